@@ -0,0 +1,325 @@
+use std::ffi::OsStr;
+#[cfg(feature = "zip")]
+use std::fs::File;
+#[cfg(feature = "tar")]
+use std::fs::{read, write};
+use std::io::Read;
+#[cfg(feature = "zip")]
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::{Component, Path, PathBuf};
+#[cfg(feature = "tar")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use glob::{MatchOptions, Pattern};
+
+#[cfg(feature = "tar")]
+use crate::Compression;
+#[cfg(feature = "tar")]
+use crate::compression::{pack, unpack};
+use crate::from_file::from_bytes;
+use crate::to_file::to_bytes;
+use crate::{Error, FromFile, ToFile, WriteOptions};
+
+const GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Supported archive formats.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    #[cfg(feature = "zip")]
+    Zip,
+    #[cfg(feature = "tar")]
+    Tar(Option<Compression>),
+}
+
+impl Kind {
+    /// Determines the archive format from the file extension(s) of the given path.
+    ///
+    /// Compressed TAR archives are recognized by a compression extension
+    /// following the `tar` extension, e.g. `bundle.tar.gz`, or by `tgz`.
+    fn detect(path: &Path) -> crate::Result<Self> {
+        let extension = path
+            .extension()
+            .map(OsStr::to_ascii_lowercase)
+            .ok_or(Error::NoFileExtensionsSpecified)?;
+
+        match extension.as_encoded_bytes() {
+            #[cfg(feature = "zip")]
+            b"zip" => return Ok(Self::Zip),
+            #[cfg(feature = "tar")]
+            b"tar" => return Ok(Self::Tar(None)),
+            #[cfg(all(feature = "tar", feature = "gzip"))]
+            b"tgz" => return Ok(Self::Tar(Some(Compression::Gzip))),
+            _ => {}
+        }
+
+        #[cfg(feature = "tar")]
+        if let Some(compression) = Compression::from_extension(&extension)
+            && path
+                .file_stem()
+                .map(Path::new)
+                .and_then(Path::extension)
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("tar"))
+        {
+            return Ok(Self::Tar(Some(compression)));
+        }
+
+        Err(Error::UnsupportedFileExtension(extension))
+    }
+}
+
+/// Iterator over deserialized archive entries.
+///
+/// Yields the path of each matching entry within the archive alongside its deserialized content.
+/// See [`FromFile::from_archive_glob`].
+#[derive(Debug)]
+pub struct ArchiveEntries<T> {
+    entries: std::vec::IntoIter<(PathBuf, Vec<u8>)>,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<T> Iterator for ArchiveEntries<T>
+where
+    T: FromFile,
+{
+    type Item = crate::Result<(PathBuf, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next()
+            .map(|(name, bytes)| from_bytes(&name, bytes).map(|value| (name, value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+/// Writes several serializable objects into a new ZIP or (compressed) TAR archive.
+///
+/// The format of each entry is determined by the entry's file extension.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{ArchiveWriter, FromFile};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// struct Person {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[cfg(all(feature = "zip", feature = "json", feature = "yaml"))]
+/// {
+///     let person = Person {
+///         id: 1337,
+///         name: "John Doe".to_string(),
+///     };
+///     let archive = std::env::temp_dir().join("serde_rw_archive_writer.zip");
+///     let mut writer = ArchiveWriter::create(&archive).unwrap();
+///     writer.add("person.json", &person).unwrap();
+///     writer.add("nested/person.yml", &person).unwrap();
+///     writer.finish().unwrap();
+///     assert_eq!(
+///         Person::from_archive(&archive, "nested/person.yml").unwrap(),
+///         person
+///     );
+/// }
+/// ```
+pub struct ArchiveWriter {
+    inner: Writer,
+}
+
+enum Writer {
+    #[cfg(feature = "zip")]
+    Zip(Box<zip::ZipWriter<File>>),
+    #[cfg(feature = "tar")]
+    Tar {
+        builder: tar::Builder<Vec<u8>>,
+        path: PathBuf,
+        compression: Option<Compression>,
+    },
+}
+
+impl ArchiveWriter {
+    /// Creates a new archive at the given path.
+    ///
+    /// The archive format is determined by the file extension(s) of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the archive format is not supported or the file cannot be created.
+    pub fn create(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let inner = match Kind::detect(path.as_ref())? {
+            #[cfg(feature = "zip")]
+            Kind::Zip => Writer::Zip(Box::new(zip::ZipWriter::new(File::create(path)?))),
+            #[cfg(feature = "tar")]
+            Kind::Tar(compression) => Writer::Tar {
+                builder: tar::Builder::new(Vec::new()),
+                path: path.as_ref().to_path_buf(),
+                compression,
+            },
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Serializes an object into a new entry of the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the serialization fails or the entry cannot be written.
+    pub fn add<T>(&mut self, name: impl AsRef<Path>, value: &T) -> crate::Result<&mut Self>
+    where
+        T: ToFile,
+    {
+        self.add_with(name, value, &WriteOptions::default())
+    }
+
+    /// Serializes an object into a new entry of the archive using the given options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the serialization fails or the entry cannot be written.
+    pub fn add_with<T>(
+        &mut self,
+        name: impl AsRef<Path>,
+        value: &T,
+        options: &WriteOptions,
+    ) -> crate::Result<&mut Self>
+    where
+        T: ToFile,
+    {
+        let name = name.as_ref();
+        let bytes = to_bytes(name, value, options)?;
+
+        match &mut self.inner {
+            #[cfg(feature = "zip")]
+            Writer::Zip(writer) => {
+                writer.start_file(
+                    name.to_string_lossy(),
+                    zip::write::SimpleFileOptions::default(),
+                )?;
+                writer.write_all(&bytes)?;
+            }
+            #[cfg(feature = "tar")]
+            Writer::Tar { builder, .. } => {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(bytes.len() as u64);
+                header.set_mode(0o644);
+                header.set_mtime(
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |duration| duration.as_secs()),
+                );
+                builder.append_data(&mut header, name, bytes.as_slice())?;
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Finishes writing the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the archive cannot be written.
+    pub fn finish(self) -> crate::Result<()> {
+        match self.inner {
+            #[cfg(feature = "zip")]
+            Writer::Zip(writer) => writer.finish().map(drop).map_err(Error::from),
+            #[cfg(feature = "tar")]
+            Writer::Tar {
+                builder,
+                path,
+                compression,
+            } => Ok(write(
+                path,
+                pack(builder.into_inner()?, compression, None)?,
+            )?),
+        }
+    }
+}
+
+/// Deserializes the entry with the given name from an archive.
+pub fn read_entry<T>(archive: &Path, entry: &Path) -> crate::Result<T>
+where
+    T: FromFile,
+{
+    let entry = normalize(entry);
+    let (name, bytes) = entries(archive, |name| name == entry)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::ArchiveEntryNotFound(entry.clone()))?;
+    from_bytes(&name, bytes)
+}
+
+/// Returns an iterator over all entries of an archive whose names match the given glob pattern.
+pub fn read_entries<T>(archive: &Path, pattern: &str) -> crate::Result<ArchiveEntries<T>>
+where
+    T: FromFile,
+{
+    let pattern = Pattern::new(pattern).map_err(|error| Error::InvalidPattern(Box::new(error)))?;
+    Ok(ArchiveEntries {
+        entries: entries(archive, |name| {
+            pattern.matches_path_with(name, GLOB_OPTIONS)
+        })?
+        .into_iter(),
+        target: PhantomData,
+    })
+}
+
+/// Reads the names and contents of all regular files within an archive that match the given predicate.
+fn entries(
+    archive: &Path,
+    mut predicate: impl FnMut(&Path) -> bool,
+) -> crate::Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut entries = Vec::new();
+
+    match Kind::detect(archive)? {
+        #[cfg(feature = "zip")]
+        Kind::Zip => {
+            let mut archive = zip::ZipArchive::new(File::open(archive)?)?;
+
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index)?;
+                let name = normalize(Path::new(file.name()));
+
+                if file.is_file() && predicate(&name) {
+                    let mut bytes = Vec::new();
+                    file.read_to_end(&mut bytes)?;
+                    entries.push((name, bytes));
+                }
+            }
+        }
+        #[cfg(feature = "tar")]
+        Kind::Tar(compression) => {
+            let bytes = unpack(read(archive)?, compression)?;
+
+            for entry in tar::Archive::new(bytes.as_slice()).entries()? {
+                let mut entry = entry?;
+                let name = normalize(&entry.path()?);
+
+                if entry.header().entry_type().is_file() && predicate(&name) {
+                    let mut bytes = Vec::new();
+                    entry.read_to_end(&mut bytes)?;
+                    entries.push((name, bytes));
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Strips current directory components, such as a leading `./`, from an entry name.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use tokio::fs::{File, metadata, read, remove_file, rename, write};
+use tokio::io::AsyncWriteExt;
+#[cfg(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml"))]
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+
+use crate::from_file::from_bytes;
+use crate::to_file::{temporary_path, to_bytes};
+use crate::{FromFile, ToFile, WriteOptions};
+
+/// Makes an object capable of asynchronously reading itself from a file of a specified format.
+///
+/// This is the asynchronous counterpart of [`FromFile`] based on [`tokio::fs`].
+pub trait AsyncFromFile: FromFile {
+    /// Asynchronously deserializes an object from a file dependent on its file extension.
+    ///
+    /// See [`FromFile::from_file`] for how the format and compression are determined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::AsyncFromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "json")]
+    /// {
+    ///     let runtime = tokio::runtime::Builder::new_current_thread()
+    ///         .build()
+    ///         .unwrap();
+    ///     assert_eq!(
+    ///         runtime
+    ///             .block_on(Person::from_file_async("./tests/person.json"))
+    ///             .unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    /// ```
+    fn from_file_async(
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<Self>> + Send {
+        async move { from_bytes(filename.as_ref(), read(filename.as_ref()).await?) }
+    }
+}
+
+/// Makes an object capable of asynchronously writing itself to a file of a specified format.
+///
+/// This is the asynchronous counterpart of [`ToFile`] based on [`tokio::fs`].
+pub trait AsyncToFile: ToFile {
+    /// Asynchronously serializes an object into a file dependent on its file extension.
+    ///
+    /// See [`ToFile::write_to_file`] for how the format and compression are determined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    fn write_to_file_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        write_file(filename, self, WriteOptions::default())
+    }
+
+    /// Asynchronously serializes an object into a prettified file dependent on its file extension.
+    ///
+    /// This will fall back to normal serialization if the given format does not support pretty printing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    fn write_to_file_pretty_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        write_file(filename, self, WriteOptions::default().pretty(true))
+    }
+
+    /// Asynchronously serializes an object into a file dependent on its file extension using the given options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{AsyncFromFile, AsyncToFile, WriteOptions};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "toml")]
+    /// {
+    ///     let person = Person {
+    ///         id: 1337,
+    ///         name: "John Doe".to_string(),
+    ///     };
+    ///     let filename = std::env::temp_dir().join("serde_rw_write_to_file_with_async.toml");
+    ///     let runtime = tokio::runtime::Builder::new_current_thread()
+    ///         .build()
+    ///         .unwrap();
+    ///     runtime
+    ///         .block_on(person.write_to_file_with_async(&filename, &WriteOptions::default().atomic(true)))
+    ///         .unwrap();
+    ///     assert_eq!(
+    ///         runtime.block_on(Person::from_file_async(&filename)).unwrap(),
+    ///         person
+    ///     );
+    /// }
+    /// ```
+    fn write_to_file_with_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+        options: &WriteOptions,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        write_file(filename, self, *options)
+    }
+}
+
+impl<T> AsyncFromFile for T where T: FromFile {}
+impl<T> AsyncToFile for T where T: ToFile {}
+
+/// Serializes an object and returns a future that writes it into the given file.
+///
+/// The serialization happens eagerly, so that the future does not borrow the object.
+fn write_file<T, P>(
+    filename: P,
+    value: &T,
+    options: WriteOptions,
+) -> impl Future<Output = crate::Result<()>> + Send + use<T, P>
+where
+    T: ToFile,
+    P: AsRef<Path> + Send,
+{
+    let bytes = to_bytes(filename.as_ref(), value, &options);
+    async move { write_bytes(filename.as_ref(), &bytes?, &options).await }
+}
+
+/// Asynchronously writes raw file content to the given file, atomically and keeping a backup if requested by the options.
+pub async fn write_bytes(
+    filename: &Path,
+    bytes: &[u8],
+    options: &WriteOptions,
+) -> crate::Result<()> {
+    if let Some(backup) = options.get_backup() {
+        let filename = filename.to_path_buf();
+        tokio::task::spawn_blocking(move || crate::backup::create(&filename, backup))
+            .await
+            .map_err(std::io::Error::other)??;
+    }
+
+    if !options.is_atomic() {
+        return Ok(write(filename, bytes).await?);
+    }
+
+    let temporary = temporary_path(filename);
+    let result = async {
+        let mut file = File::create(&temporary).await?;
+
+        if let Ok(metadata) = metadata(filename).await {
+            file.set_permissions(metadata.permissions()).await?;
+        }
+
+        file.write_all(bytes).await?;
+        file.sync_all().await?;
+        rename(&temporary, filename).await
+    }
+    .await;
+
+    if let Err(error) = result {
+        let _ = remove_file(&temporary).await;
+        return Err(error.into());
+    }
+
+    Ok(())
+}
+
+/// Asynchronously writes the given text to the given file atomically.
+#[cfg(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml"))]
+pub async fn write_text_file(filename: &Path, text: &str) -> crate::Result<()> {
+    write_bytes(
+        filename,
+        text.as_bytes(),
+        &WriteOptions::default().atomic(true),
+    )
+    .await
+}
+
+/// Asynchronously reads all text from the given reader.
+#[cfg(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml"))]
+pub async fn read_text<R>(mut reader: R) -> crate::Result<String>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut text = String::new();
+    reader.read_to_string(&mut text).await?;
+    Ok(text)
+}
+
+/// Asynchronously writes the given text to the given writer and flushes it.
+#[cfg(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml"))]
+pub async fn write_text<W>(mut writer: W, text: &str) -> crate::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer.write_all(text.as_bytes()).await?;
+    Ok(writer.flush().await?)
+}
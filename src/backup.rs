@@ -0,0 +1,197 @@
+use std::cmp::Reverse;
+use std::ffi::OsString;
+use std::fs::{copy, create_dir_all, read_dir, remove_file, rename};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Error;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Specifies how [`WriteOptions::backup`](crate::WriteOptions::backup) keeps previous versions of a file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Backup {
+    /// Keeps the previous version as `<file>.bak`, e.g. `config.toml.bak`.
+    Single,
+    /// Keeps the given number of previous versions as `<file>.1`, the most recent one, up to `<file>.<n>`.
+    ///
+    /// Older versions beyond that number, e.g. after lowering it, are removed.
+    Numbered(usize),
+    /// Keeps the given number of previous versions within the directory `<file>.snapshots` next to the file.
+    ///
+    /// Each snapshot is named after the time at which it was taken, e.g. `20261018T123000.000000000Z`.
+    Snapshots(usize),
+}
+
+/// Keeps the current version of a file before it is overwritten, if the file exists.
+pub fn create(filename: &Path, backup: Backup) -> crate::Result<()> {
+    if !filename.is_file() {
+        return Ok(());
+    }
+
+    match backup {
+        Backup::Single => {
+            copy(filename, with_suffix(filename, "bak"))?;
+        }
+        Backup::Numbered(count) => {
+            // Shift the existing backups from the oldest one on, so that none is overwritten.
+            for (number, backup) in numbered(filename)? {
+                if number < count {
+                    rename(backup, with_suffix(filename, &(number + 1).to_string()))?;
+                } else {
+                    remove_file(backup)?;
+                }
+            }
+
+            if count > 0 {
+                copy(filename, with_suffix(filename, "1"))?;
+            }
+        }
+        Backup::Snapshots(count) => {
+            let directory = with_suffix(filename, "snapshots");
+
+            if count > 0 {
+                create_dir_all(&directory)?;
+                copy(filename, directory.join(timestamp(SystemTime::now())))?;
+            }
+
+            if directory.is_dir() {
+                for snapshot in snapshots(&directory)?.into_iter().skip(count) {
+                    remove_file(snapshot)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the path of a previous version of a file.
+///
+/// The number counts from the most recent version, which is `1`.
+/// Numbered backups take precedence over `.bak` files and snapshots.
+pub fn find(filename: &Path, number: usize) -> crate::Result<PathBuf> {
+    let not_found = || Error::BackupNotFound {
+        path: filename.to_path_buf(),
+        number,
+    };
+
+    if number == 0 {
+        return Err(not_found());
+    }
+
+    let numbered = with_suffix(filename, &number.to_string());
+
+    if numbered.is_file() {
+        return Ok(numbered);
+    }
+
+    let single = with_suffix(filename, "bak");
+
+    if number == 1 && single.is_file() {
+        return Ok(single);
+    }
+
+    let directory = with_suffix(filename, "snapshots");
+
+    if !directory.is_dir() {
+        return Err(not_found());
+    }
+
+    snapshots(&directory)?
+        .into_iter()
+        .nth(number - 1)
+        .ok_or_else(not_found)
+}
+
+/// Returns the numbered backups of a file alongside their numbers, the oldest one first.
+fn numbered(filename: &Path) -> crate::Result<Vec<(usize, PathBuf)>> {
+    let Some(name) = filename.file_name() else {
+        return Ok(Vec::new());
+    };
+    let directory = match filename.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut backups = Vec::new();
+
+    for entry in read_dir(directory)? {
+        let path = entry?.path();
+        let number = path
+            .file_name()
+            .and_then(|file_name| {
+                file_name
+                    .as_encoded_bytes()
+                    .strip_prefix(name.as_encoded_bytes())?
+                    .strip_prefix(b".")
+            })
+            .filter(|suffix| {
+                suffix
+                    .first()
+                    .is_some_and(|digit| (b'1'..=b'9').contains(digit))
+            })
+            .and_then(|suffix| std::str::from_utf8(suffix).ok()?.parse::<usize>().ok());
+
+        if let Some(number) = number
+            && path.is_file()
+        {
+            backups.push((number, path));
+        }
+    }
+
+    backups.sort_unstable_by_key(|(number, _)| Reverse(*number));
+    Ok(backups)
+}
+
+/// Returns the snapshots within a directory, the most recent one first.
+fn snapshots(directory: &Path) -> crate::Result<Vec<PathBuf>> {
+    let mut snapshots = read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    snapshots.retain(|path| path.is_file());
+    snapshots.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    Ok(snapshots)
+}
+
+/// Appends a suffix to the file name of a path, e.g. `config.toml` becomes `config.toml.bak`.
+fn with_suffix(filename: &Path, suffix: &str) -> PathBuf {
+    let mut name = filename.file_name().map(OsString::from).unwrap_or_default();
+    name.push(".");
+    name.push(suffix);
+    filename.with_file_name(name)
+}
+
+/// Formats a time as a compact UTC timestamp, which sorts chronologically.
+fn timestamp(time: SystemTime) -> String {
+    let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = duration.as_secs() % SECONDS_PER_DAY;
+    let (year, month, day) = date(duration.as_secs() / SECONDS_PER_DAY);
+    format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}.{:09}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        duration.subsec_nanos()
+    )
+}
+
+/// Converts days since the Unix epoch into a year, month and day of the Gregorian calendar.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+const fn date(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
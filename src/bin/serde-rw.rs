@@ -0,0 +1,191 @@
+//! Command-line interface to convert, format and validate files in the formats supported by `serde_rw`.
+#![allow(unused_crate_dependencies)]
+
+use std::ffi::OsStr;
+use std::io::{ErrorKind, Read, Write, stdin, stdout};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde_rw::{Compression, Error, Format, FromFile, ToFile, Value, WriteOptions};
+
+const STDIO: &str = "-";
+
+/// Convert, format and validate JSON, TOML, XML and YAML files.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert a file into another format.
+    Convert {
+        /// The input file or `-` for stdin.
+        input: PathBuf,
+        /// The output file or `-` for stdout.
+        output: PathBuf,
+        /// The input format, if it cannot be determined from the file extension.
+        #[arg(long, value_parser = parse_format)]
+        from: Option<Format>,
+        /// The output format, if it cannot be determined from the file extension.
+        #[arg(long, value_parser = parse_format)]
+        to: Option<Format>,
+        /// Prettify the output, if the format supports it.
+        #[arg(long, short)]
+        pretty: bool,
+        /// The name of the root element of XML output, which defaults to the name of the output file.
+        #[arg(long)]
+        root: Option<String>,
+    },
+    /// Pretty-print files in place or stdin to stdout.
+    Fmt {
+        /// The files to format or `-` for stdin.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// The format, if it cannot be determined from the file extension.
+        #[arg(long, value_parser = parse_format)]
+        format: Option<Format>,
+    },
+    /// Check the syntax of files.
+    Validate {
+        /// The files to validate or `-` for stdin.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// The format, if it cannot be determined from the file extension.
+        #[arg(long, value_parser = parse_format)]
+        format: Option<Format>,
+    },
+}
+
+fn main() -> ExitCode {
+    match Args::parse().command {
+        Command::Convert {
+            input,
+            output,
+            from,
+            to,
+            pretty,
+            root,
+        } => {
+            // The input format only applies to stdout, since files are written in the format of their extension.
+            let to = to.or_else(|| from.filter(|_| output == Path::new(STDIO)));
+            let options = options(&output, root, pretty);
+            report(
+                &input,
+                read(&input, from).and_then(|value| write(&output, to, &value, &options)),
+            )
+        }
+        Command::Fmt { files, format } => files.iter().fold(ExitCode::SUCCESS, |code, file| {
+            let result = read(file, format)
+                .and_then(|value| write(file, format, &value, &options(file, None, true)));
+            merge(code, report(file, result))
+        }),
+        Command::Validate { files, format } => {
+            files.iter().fold(ExitCode::SUCCESS, |code, file| {
+                merge(code, report(file, read(file, format).map(drop)))
+            })
+        }
+    }
+}
+
+/// Reads a value from the given file or stdin.
+fn read(path: &Path, format: Option<Format>) -> serde_rw::Result<Value> {
+    if path == Path::new(STDIO) {
+        let mut text = String::new();
+        stdin().read_to_string(&mut text)?;
+        return required(format)?.deserialize(&text);
+    }
+
+    format.map_or_else(
+        || Value::from_file(path),
+        |format| Value::from_file_as(path, format),
+    )
+}
+
+/// Writes a value to the given file or stdout.
+fn write(
+    path: &Path,
+    format: Option<Format>,
+    value: &Value,
+    options: &WriteOptions,
+) -> serde_rw::Result<()> {
+    if path == Path::new(STDIO) {
+        let mut text = required(format)?.serialize_with(value, options)?;
+
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+
+        return Ok(stdout().write_all(text.as_bytes())?);
+    }
+
+    format.map_or_else(
+        || value.write_to_file_with(path, options),
+        |format| value.write_to_file_as(path, format, options),
+    )
+}
+
+/// Returns the options to write the given file or stdout with.
+///
+/// The root element of XML output is named after the file, unless its name is given.
+fn options(path: &Path, root: Option<String>, pretty: bool) -> WriteOptions {
+    let options = WriteOptions::default().pretty(pretty).atomic(true);
+    // Write options only hold static names, so the name is leaked, which happens at most once per file.
+    root.or_else(|| root_name(path))
+        .map_or(options, |root| options.xml_root(root.leak()))
+}
+
+/// Returns the name of the file without its extensions, if it is not stdout.
+fn root_name(path: &Path) -> Option<String> {
+    if path == Path::new(STDIO) {
+        return None;
+    }
+
+    let stem = Path::new(path.file_stem()?);
+    let stem = if path
+        .extension()
+        .and_then(Compression::from_extension)
+        .is_some()
+    {
+        stem.file_stem()?
+    } else {
+        stem.as_os_str()
+    };
+    stem.to_str().map(ToString::to_string)
+}
+
+/// Returns the given format or an error if it is required but has not been specified.
+fn required(format: Option<Format>) -> serde_rw::Result<Format> {
+    format.ok_or_else(|| {
+        Error::Io(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            "the format of stdin and stdout must be specified via --from, --to or --format",
+        ))
+    })
+}
+
+fn parse_format(name: &str) -> Result<Format, String> {
+    Format::from_extension(OsStr::new(name)).ok_or_else(|| format!("unsupported format: {name}"))
+}
+
+/// Prints an error, if any, and returns the respective exit code.
+fn report(path: &Path, result: serde_rw::Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}: {error}", path.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn merge(code: ExitCode, other: ExitCode) -> ExitCode {
+    if code == ExitCode::SUCCESS {
+        other
+    } else {
+        code
+    }
+}
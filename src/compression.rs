@@ -0,0 +1,233 @@
+use std::ffi::OsStr;
+#[cfg(any(
+    feature = "gzip",
+    feature = "zstd",
+    feature = "xz",
+    feature = "bzip2",
+    feature = "lz4"
+))]
+use std::io::Read;
+#[cfg(any(feature = "gzip", feature = "xz", feature = "bzip2", feature = "lz4"))]
+use std::io::Write;
+use std::path::Path;
+
+use crate::Format;
+
+/// Supported compression formats.
+///
+/// Compressed files are recognized by a trailing compression extension,
+/// e.g. `data.json.gz` or `config.toml.zst`, and are transparently
+/// decompressed on read and compressed on write.
+#[allow(clippy::empty_enums)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Compression {
+    /// Gzip compression via the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// Zstandard compression via the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd,
+    /// XZ compression via the `xz` feature.
+    #[cfg(feature = "xz")]
+    Xz,
+    /// Bzip2 compression via the `bzip2` feature.
+    #[cfg(feature = "bzip2")]
+    Bzip2,
+    /// LZ4 frame compression via the `lz4` feature.
+    #[cfg(feature = "lz4")]
+    Lz4,
+}
+
+impl Compression {
+    /// Returns the compression indicated by the given file extension, if it is supported.
+    ///
+    /// The comparison is case-insensitive.
+    #[must_use]
+    pub fn from_extension(extension: &OsStr) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_encoded_bytes() {
+            #[cfg(feature = "gzip")]
+            b"gz" | b"gzip" => Some(Self::Gzip),
+            #[cfg(feature = "zstd")]
+            b"zst" | b"zstd" => Some(Self::Zstd),
+            #[cfg(feature = "xz")]
+            b"xz" => Some(Self::Xz),
+            #[cfg(feature = "bzip2")]
+            b"bz2" => Some(Self::Bzip2),
+            #[cfg(feature = "lz4")]
+            b"lz4" => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+
+    /// Returns the compression indicated by the magic bytes at the start of the given data.
+    #[must_use]
+    pub const fn from_magic(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            #[cfg(feature = "gzip")]
+            [0x1f, 0x8b, ..] => Some(Self::Gzip),
+            #[cfg(feature = "zstd")]
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Self::Zstd),
+            #[cfg(feature = "xz")]
+            [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => Some(Self::Xz),
+            #[cfg(feature = "bzip2")]
+            [b'B', b'Z', b'h', ..] => Some(Self::Bzip2),
+            #[cfg(feature = "lz4")]
+            [0x04, 0x22, 0x4d, 0x18, ..] => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+
+    /// Decompresses the given data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the data cannot be decompressed.
+    #[cfg_attr(
+        not(any(
+            feature = "gzip",
+            feature = "zstd",
+            feature = "xz",
+            feature = "bzip2",
+            feature = "lz4"
+        )),
+        allow(unused_variables, clippy::missing_const_for_fn)
+    )]
+    pub fn decompress(self, bytes: &[u8]) -> crate::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => read_all(flate2::read::MultiGzDecoder::new(bytes)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => read_all(zstd::stream::read::Decoder::new(bytes)?),
+            #[cfg(feature = "xz")]
+            Self::Xz => read_all(liblzma::read::XzDecoder::new_multi_decoder(bytes)),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => read_all(bzip2::read::MultiBzDecoder::new(bytes)),
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => read_all(lz4_flex::frame::FrameDecoder::new(bytes)),
+        }
+    }
+
+    /// Compresses the given data.
+    ///
+    /// If no `level` is given, the compression's default level is used.
+    /// The level is clamped to the range supported by the respective compression.
+    /// LZ4 does not support compression levels, so the level is ignored for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the data cannot be compressed.
+    #[cfg_attr(
+        not(any(
+            feature = "gzip",
+            feature = "zstd",
+            feature = "xz",
+            feature = "bzip2",
+            feature = "lz4"
+        )),
+        allow(unused_variables, clippy::missing_const_for_fn)
+    )]
+    pub fn compress(self, bytes: &[u8], level: Option<u32>) -> crate::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                let level = level.map_or_else(flate2::Compression::default, |level| {
+                    flate2::Compression::new(level.min(9))
+                });
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), level);
+                encoder.write_all(bytes)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd => {
+                let level = level.map_or(zstd::DEFAULT_COMPRESSION_LEVEL, |level| {
+                    i32::try_from(level.clamp(1, 22)).unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL)
+                });
+                Ok(zstd::stream::encode_all(bytes, level)?)
+            }
+            #[cfg(feature = "xz")]
+            Self::Xz => {
+                let mut encoder = liblzma::write::XzEncoder::new(
+                    Vec::new(),
+                    level.map_or(6, |level| level.min(9)),
+                );
+                encoder.write_all(bytes)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => {
+                let level = level.map_or_else(bzip2::Compression::default, |level| {
+                    bzip2::Compression::new(level.clamp(1, 9))
+                });
+                let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), level);
+                encoder.write_all(bytes)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => {
+                // LZ4 frames have no compression levels.
+                let _ = level;
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                encoder.write_all(bytes)?;
+                encoder
+                    .finish()
+                    .map_err(|error| std::io::Error::other(error).into())
+            }
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "gzip",
+    feature = "zstd",
+    feature = "xz",
+    feature = "bzip2",
+    feature = "lz4"
+))]
+fn read_all(mut reader: impl Read) -> crate::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Returns the compression of raw file content whose file name does not indicate its format.
+///
+/// A trailing compression extension takes precedence over the content's magic bytes,
+/// which are only considered if the file has no extension or an unknown one,
+/// so that plain files which happen to start with magic bytes are read as they are.
+pub fn sniff(path: &Path, bytes: &[u8]) -> Option<Compression> {
+    match path.extension() {
+        Some(extension) if Format::from_extension(extension).is_some() => None,
+        Some(extension) => {
+            Compression::from_extension(extension).or_else(|| Compression::from_magic(bytes))
+        }
+        None => Compression::from_magic(bytes),
+    }
+}
+
+/// Decompresses raw file content if a compression is given.
+pub fn unpack(bytes: Vec<u8>, compression: Option<Compression>) -> crate::Result<Vec<u8>> {
+    match compression {
+        Some(compression) => compression.decompress(&bytes),
+        None => Ok(bytes),
+    }
+}
+
+/// Compresses raw file content if a compression is given.
+pub fn pack(
+    bytes: Vec<u8>,
+    compression: Option<Compression>,
+    level: Option<u32>,
+) -> crate::Result<Vec<u8>> {
+    match compression {
+        Some(compression) => compression.compress(&bytes, level),
+        None => Ok(bytes),
+    }
+}
+
+/// Decodes raw file content into text.
+///
+/// See [`unpack`] for how the content is decompressed.
+pub fn decode(bytes: Vec<u8>, compression: Option<Compression>) -> crate::Result<String> {
+    String::from_utf8(unpack(bytes, compression)?)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error).into())
+}
@@ -0,0 +1,292 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+use crate::path::{Segment, join, pointer};
+use crate::{FromFile, Value};
+
+/// Compares the content of two files, which may be of different formats.
+///
+/// The files are loaded into [`Value`]s, so that e.g. a TOML and a YAML file holding the same data are equal.
+///
+/// # Errors
+///
+/// Returns an [`Error`](crate::Error) if either file cannot be read or parsed.
+///
+/// # Examples
+/// ```
+/// use serde_rw::diff;
+///
+/// #[cfg(all(feature = "diff", feature = "toml", feature = "yaml"))]
+/// {
+///     assert!(diff("./tests/person.toml", "./tests/person.yml").unwrap().is_empty());
+///
+///     let filename = std::env::temp_dir().join("serde_rw_diff.yml");
+///     std::fs::write(&filename, "id: 1337\nname: Jane Doe\naliases: [JD]\n").unwrap();
+///
+///     let diff = diff("./tests/person.toml", &filename).unwrap();
+///     assert_eq!(
+///         diff.to_string(),
+///         "-name: \"John Doe\"\n+name: \"Jane Doe\"\n+aliases: [\"JD\"]\n"
+///     );
+/// }
+/// ```
+pub fn diff(old: impl AsRef<Path>, new: impl AsRef<Path>) -> crate::Result<Diff> {
+    Ok(Diff::between(
+        &Value::from_file(old)?,
+        &Value::from_file(new)?,
+    ))
+}
+
+/// The differences between two values.
+///
+/// Its [`Display`] implementation renders the differences in the style of a unified diff,
+/// i.e. removed values are prefixed with `-` and added values with `+`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Compares two values.
+    ///
+    /// Maps are compared by key and sequences by index,
+    /// so that each change refers to the innermost differing value.
+    /// Since formats represent the same data differently, numbers are compared regardless of their width
+    /// and datetimes are equal to strings holding the same text.
+    #[must_use]
+    pub fn between(old: &Value, new: &Value) -> Self {
+        let mut changes = Vec::new();
+        compare(&mut Vec::new(), old, new, &mut changes);
+        Self { changes }
+    }
+
+    /// Returns the changes.
+    #[must_use]
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns `true` if the values are equal.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes as a JSON Patch (RFC 6902), which turns the old into the new value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if a value is not representable in JSON.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Diff, Value};
+    ///
+    /// #[cfg(feature = "diff")]
+    /// {
+    ///     let old = Value::from(vec![Value::from(1_i64), Value::from(2_i64)]);
+    ///     let new = Value::from(vec![Value::from(3_i64)]);
+    ///     assert_eq!(
+    ///         Diff::between(&old, &new).to_json_patch().unwrap(),
+    ///         serde_json::json!([
+    ///             {"op": "replace", "path": "/0", "value": 3},
+    ///             {"op": "remove", "path": "/1"},
+    ///         ])
+    ///     );
+    /// }
+    /// ```
+    pub fn to_json_patch(&self) -> crate::Result<serde_json::Value> {
+        self.changes
+            .iter()
+            .map(|change| {
+                let mut operation = serde_json::Map::new();
+                let op = match change.kind() {
+                    ChangeKind::Added => "add",
+                    ChangeKind::Removed => "remove",
+                    ChangeKind::Changed => "replace",
+                };
+                operation.insert("op".to_string(), op.into());
+                operation.insert("path".to_string(), change.pointer.clone().into());
+
+                if let Some(value) = &change.new {
+                    operation.insert("value".to_string(), value.clone().try_into()?);
+                }
+
+                Ok(serde_json::Value::Object(operation))
+            })
+            .collect()
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.changes.iter().try_for_each(|change| {
+            if let Some(old) = &change.old {
+                writeln!(f, "-{}", Line(&change.path, old))?;
+            }
+
+            if let Some(new) = &change.new {
+                writeln!(f, "+{}", Line(&change.path, new))?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// A value that was added, removed or changed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    path: String,
+    pointer: String,
+    old: Option<Value>,
+    new: Option<Value>,
+}
+
+impl Change {
+    /// Returns the path of the value, e.g. `servers[0].port`.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the path of the value as a JSON Pointer, e.g. `/servers/0/port`.
+    #[must_use]
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Returns whether the value was added, removed or changed.
+    #[must_use]
+    pub const fn kind(&self) -> ChangeKind {
+        match (&self.old, &self.new) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        }
+    }
+
+    /// Returns the old value, unless the value was added.
+    #[must_use]
+    pub const fn old_value(&self) -> Option<&Value> {
+        self.old.as_ref()
+    }
+
+    /// Returns the new value, unless the value was removed.
+    #[must_use]
+    pub const fn new_value(&self) -> Option<&Value> {
+        self.new.as_ref()
+    }
+}
+
+/// The kind of a [`Change`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChangeKind {
+    /// The value exists only in the new value.
+    Added,
+    /// The value exists only in the old value.
+    Removed,
+    /// The value differs between the old and the new value.
+    Changed,
+}
+
+/// A line of the rendered diff, consisting of a path and a value.
+struct Line<'change>(&'change str, &'change Value);
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = serde_json::to_string(self.1).map_err(|_| fmt::Error)?;
+
+        if self.0.is_empty() {
+            write!(f, "{value}")
+        } else {
+            write!(f, "{}: {value}", self.0)
+        }
+    }
+}
+
+/// Collects the changes between two values at the given path.
+///
+/// Sequence elements are removed from the end, so that the indices of preceding changes remain valid.
+fn compare<'value>(
+    path: &mut Vec<Segment<'value>>,
+    old: &'value Value,
+    new: &'value Value,
+    changes: &mut Vec<Change>,
+) {
+    match (old, new) {
+        (old, new) if equivalent(old, new) => {}
+        (Value::Map(old), Value::Map(new)) => {
+            for (key, value) in old.iter().filter(|(key, _)| !new.contains_key(*key)) {
+                path.push(Segment::Key(key));
+                changes.push(change(path, Some(value), None));
+                path.pop();
+            }
+
+            for (key, value) in new {
+                path.push(Segment::Key(key));
+
+                match old.get(key) {
+                    Some(old) => compare(path, old, value, changes),
+                    None => changes.push(change(path, None, Some(value))),
+                }
+
+                path.pop();
+            }
+        }
+        (Value::Sequence(old), Value::Sequence(new)) => {
+            for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                path.push(Segment::Index(index));
+                compare(path, old, new, changes);
+                path.pop();
+            }
+
+            for (index, value) in old.iter().enumerate().skip(new.len()).rev() {
+                path.push(Segment::Index(index));
+                changes.push(change(path, Some(value), None));
+                path.pop();
+            }
+
+            for (index, value) in new.iter().enumerate().skip(old.len()) {
+                path.push(Segment::Index(index));
+                changes.push(change(path, None, Some(value)));
+                path.pop();
+            }
+        }
+        (old, new) => changes.push(change(path, Some(old), Some(new))),
+    }
+}
+
+fn change(path: &[Segment<'_>], old: Option<&Value>, new: Option<&Value>) -> Change {
+    Change {
+        path: join(path),
+        pointer: pointer(path),
+        old: old.cloned(),
+        new: new.cloned(),
+    }
+}
+
+/// Determines whether two values hold the same data, regardless of how their formats represent it.
+fn equivalent(old: &Value, new: &Value) -> bool {
+    match (old, new) {
+        (Value::Integer(old), Value::Integer(new)) => match (old.as_i128(), new.as_i128()) {
+            (Some(old), Some(new)) => old == new,
+            _ => old == new,
+        },
+        #[allow(clippy::float_cmp)]
+        (Value::Float(old), Value::Float(new)) => old.as_f64() == new.as_f64(),
+        (Value::Datetime(old) | Value::String(old), Value::Datetime(new) | Value::String(new)) => {
+            old == new
+        }
+        (Value::Sequence(old), Value::Sequence(new)) => {
+            old.len() == new.len() && old.iter().zip(new).all(|(old, new)| equivalent(old, new))
+        }
+        (Value::Map(old), Value::Map(new)) => {
+            old.len() == new.len()
+                && old
+                    .iter()
+                    .all(|(key, old)| new.get(key).is_some_and(|new| equivalent(old, new)))
+        }
+        (old, new) => old == new,
+    }
+}
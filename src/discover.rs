@@ -0,0 +1,217 @@
+use std::env::{current_dir, var_os};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf, absolute};
+
+use crate::{Error, Format, FromFile, Layered, Precedence};
+
+const DEFAULT_FILE_STEM: &str = "config";
+const EXTENSIONS: [&str; 5] = ["toml", "yaml", "yml", "json", "xml"];
+const SYSTEM_CONFIG_DIR: &str = "/etc";
+const DEFAULT_XDG_CONFIG_DIRS: &str = "/etc/xdg";
+
+/// Discovers and loads an application's configuration file with the default settings.
+///
+/// See [`Discovery`] for the locations that are searched.
+///
+/// # Errors
+///
+/// Returns an [`Error::NoConfigFound`] listing all paths tried if no configuration file exists
+/// and an [`Error`] if the configuration file cannot be loaded.
+pub fn discover<T>(app: &str) -> crate::Result<Discovered<T>>
+where
+    T: FromFile,
+{
+    Discovery::new(app).load()
+}
+
+/// Searches well-known locations for an application's configuration file.
+///
+/// The following directories are searched in order:
+///
+/// 1. `$XDG_CONFIG_HOME/<app>`, defaulting to `~/.config/<app>`
+/// 2. `<dir>/<app>` for each directory in `$XDG_CONFIG_DIRS`, defaulting to `/etc/xdg`
+/// 3. `/etc/<app>`
+/// 4. the current directory and its parents
+///
+/// Within each directory, a file named `config` with each of the enabled formats' extensions is tried,
+/// e.g. `config.toml`, `config.yaml` and `config.json`.
+///
+/// By default, the first file found is loaded.
+/// If all files found are merged, files found earlier take precedence over those found later.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Discovery, Error};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Manifest {
+///     package: Package,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Package {
+///     name: String,
+/// }
+///
+/// #[cfg(feature = "toml")]
+/// {
+///     // Finds `Cargo.toml` within the current directory.
+///     let discovered = Discovery::new("serde_rw")
+///         .file_stem("Cargo")
+///         .load::<Manifest>()
+///         .unwrap();
+///     assert!(discovered.sources()[0].ends_with("Cargo.toml"));
+///     assert_eq!(discovered.value().package.name, "serde_rw");
+///
+///     let error = Discovery::new("serde_rw")
+///         .file_stem("nonexistent")
+///         .load::<Manifest>()
+///         .unwrap_err();
+///     assert!(matches!(error, Error::NoConfigFound(tried) if !tried.is_empty()));
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Discovery {
+    app: String,
+    file_stem: String,
+    merge_all: bool,
+}
+
+impl Discovery {
+    /// Creates a discovery for the given application name.
+    #[must_use]
+    pub fn new(app: impl Into<String>) -> Self {
+        Self {
+            app: app.into(),
+            file_stem: DEFAULT_FILE_STEM.to_string(),
+            merge_all: false,
+        }
+    }
+
+    /// Sets the name of the configuration file without extension.
+    ///
+    /// Defaults to `config`.
+    #[must_use]
+    pub fn file_stem(mut self, file_stem: impl Into<String>) -> Self {
+        self.file_stem = file_stem.into();
+        self
+    }
+
+    /// Sets whether all files found shall be merged instead of loading only the first one.
+    #[must_use]
+    pub const fn merge_all(mut self, merge_all: bool) -> Self {
+        self.merge_all = merge_all;
+        self
+    }
+
+    /// Returns the directories to search in order.
+    #[must_use]
+    pub fn directories(&self) -> Vec<PathBuf> {
+        let mut directories = Vec::new();
+
+        if let Some(config_home) = var_os("XDG_CONFIG_HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        {
+            directories.push(config_home.join(&self.app));
+        }
+
+        let config_dirs = var_os("XDG_CONFIG_DIRS")
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| DEFAULT_XDG_CONFIG_DIRS.into());
+        directories.extend(
+            std::env::split_paths(&config_dirs)
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .map(|dir| dir.join(&self.app)),
+        );
+        directories.push(Path::new(SYSTEM_CONFIG_DIR).join(&self.app));
+
+        if let Ok(current) = current_dir().and_then(absolute) {
+            directories.extend(current.ancestors().map(Path::to_path_buf));
+        }
+
+        directories
+    }
+
+    /// Returns all candidate files in order.
+    #[must_use]
+    pub fn candidates(&self) -> Vec<PathBuf> {
+        self.directories()
+            .into_iter()
+            .flat_map(|directory| {
+                EXTENSIONS
+                    .into_iter()
+                    .filter(|extension| Format::from_extension(OsStr::new(extension)).is_some())
+                    .map(move |extension| directory.join(format!("{}.{extension}", self.file_stem)))
+            })
+            .collect()
+    }
+
+    /// Discovers and loads the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::NoConfigFound`] listing all paths tried if no configuration file exists
+    /// and an [`Error`] if the configuration file cannot be loaded.
+    pub fn load<T>(&self) -> crate::Result<Discovered<T>>
+    where
+        T: FromFile,
+    {
+        let candidates = self.candidates();
+        let mut sources = candidates.iter().filter(|candidate| candidate.is_file());
+
+        if !self.merge_all {
+            return match sources.next() {
+                Some(source) => Ok(Discovered {
+                    value: T::from_file(source)?,
+                    sources: vec![source.clone()],
+                }),
+                None => Err(Error::NoConfigFound(candidates)),
+            };
+        }
+
+        let sources: Vec<PathBuf> = sources.cloned().collect();
+
+        if sources.is_empty() {
+            return Err(Error::NoConfigFound(candidates));
+        }
+
+        let value = sources
+            .iter()
+            .fold(
+                Layered::new().precedence(Precedence::FirstWins),
+                Layered::file,
+            )
+            .load()?;
+        Ok(Discovered { value, sources })
+    }
+}
+
+/// A discovered configuration alongside the files it was loaded from.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Discovered<T> {
+    value: T,
+    sources: Vec<PathBuf>,
+}
+
+impl<T> Discovered<T> {
+    /// Returns the loaded configuration.
+    #[must_use]
+    pub const fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the loaded configuration, discarding its sources.
+    #[must_use]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Returns the files the configuration was loaded from in order of precedence.
+    #[must_use]
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use crate::{DuplicateKey, Error, Format};
+
+/// Returns an [`Error::DuplicateKeys`] if there are any duplicate keys.
+pub fn deny(duplicate_keys: Vec<DuplicateKey>) -> crate::Result<()> {
+    if duplicate_keys.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DuplicateKeys(duplicate_keys))
+    }
+}
+
+/// Finds keys that occur more than once within the same map of the given text.
+///
+/// Syntax errors are ignored, since they are reported by the deserialization.
+/// TOML is not scanned, since its parser rejects duplicate keys itself.
+/// Within XML, only attributes are considered keys,
+/// as repeated child elements are deserialized into sequences.
+#[cfg_attr(
+    not(any(feature = "json", feature = "xml", feature = "yaml")),
+    allow(unused_variables, clippy::missing_const_for_fn)
+)]
+pub fn find(format: Format, text: &str) -> Vec<DuplicateKey> {
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => json(text),
+        #[cfg(feature = "toml")]
+        Format::Toml => Vec::new(),
+        #[cfg(feature = "xml")]
+        Format::Xml => xml(text),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => yaml(text),
+    }
+}
+
+/// Scans JSON text for duplicate keys.
+#[cfg(feature = "json")]
+fn json(text: &str) -> Vec<DuplicateKey> {
+    let mut tracker = Tracker::default();
+    let mut chars = text.char_indices().peekable();
+    let mut line = 1;
+
+    while let Some((start, char)) = chars.next() {
+        match char {
+            '\n' => line += 1,
+            '{' => tracker.start(Frame::map()),
+            '[' => tracker.start(Frame::Sequence(0)),
+            '}' | ']' => tracker.end(),
+            '"' => {
+                let mut escaped = false;
+                let end = chars
+                    .by_ref()
+                    .find(|&(_, char)| {
+                        let end = char == '"' && !escaped;
+                        escaped = char == '\\' && !escaped;
+                        end
+                    })
+                    .map_or(text.len(), |(end, _)| end + 1);
+                let key = text
+                    .get(start..end)
+                    .and_then(|literal| serde_json::from_str(literal).ok());
+                tracker.leaf(key, line);
+            }
+            char if char.is_whitespace() || char == ':' || char == ',' => {}
+            _ => {
+                // Skip the remainder of a number, `true`, `false` or `null`.
+                while chars
+                    .next_if(|&(_, char)| char.is_alphanumeric() || "+-.".contains(char))
+                    .is_some()
+                {}
+                tracker.leaf(None, line);
+            }
+        }
+    }
+
+    tracker.duplicates
+}
+
+/// Scans YAML text for duplicate keys.
+#[cfg(feature = "yaml")]
+fn yaml(text: &str) -> Vec<DuplicateKey> {
+    let mut tracker = Tracker::default();
+    // Syntax errors are reported by the deserialization.
+    let _ = yaml_rust2::parser::Parser::new_from_str(text).load(&mut tracker, true);
+    tracker.duplicates
+}
+
+/// Scans XML text for duplicate attributes.
+#[cfg(feature = "xml")]
+fn xml(text: &str) -> Vec<DuplicateKey> {
+    use quick_xml::events::Event;
+    use quick_xml::events::attributes::Attribute;
+
+    let mut reader = quick_xml::Reader::from_str(text);
+    let mut elements: Vec<String> = Vec::new();
+    let mut duplicates = Vec::new();
+
+    loop {
+        let position = reader.buffer_position();
+        let (element, empty) = match reader.read_event() {
+            Ok(Event::Start(element)) => (element, false),
+            Ok(Event::Empty(element)) => (element, true),
+            Ok(Event::End(_)) => {
+                elements.pop();
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => continue,
+        };
+        let name = String::from_utf8_lossy(element.name().as_ref()).into_owned();
+        let line = line_at(text, position);
+        let mut names = HashMap::new();
+
+        for Attribute { key, .. } in element
+            .attributes()
+            .with_checks(false)
+            .filter_map(Result::ok)
+        {
+            let key = format!("@{}", String::from_utf8_lossy(key.as_ref()));
+
+            if let Some(first_line) = names.insert(key.clone(), line) {
+                let mut path = elements.clone();
+                path.extend([name.clone(), key]);
+                duplicates.push(DuplicateKey::new(path.join("."), first_line, line));
+            }
+        }
+
+        if !empty {
+            elements.push(name);
+        }
+    }
+
+    duplicates
+}
+
+#[cfg(feature = "yaml")]
+impl yaml_rust2::parser::MarkedEventReceiver for Tracker {
+    fn on_event(&mut self, event: yaml_rust2::Event, mark: yaml_rust2::scanner::Marker) {
+        use yaml_rust2::Event;
+
+        match event {
+            Event::DocumentStart => self.frames.clear(),
+            Event::Scalar(value, ..) => self.leaf(Some(value), mark.line()),
+            Event::Alias(_) => self.leaf(None, mark.line()),
+            Event::MappingStart(..) => self.start(Frame::map()),
+            Event::SequenceStart(..) => self.start(Frame::Sequence(0)),
+            Event::MappingEnd | Event::SequenceEnd => self.end(),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the line of the given byte position within the text.
+#[cfg(feature = "xml")]
+fn line_at(text: &str, position: u64) -> usize {
+    text.bytes()
+        .take(usize::try_from(position).unwrap_or(usize::MAX))
+        .filter(|&byte| byte == b'\n')
+        .count()
+        + 1
+}
+
+/// Tracks the keys of nested maps and sequences.
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Debug, Default)]
+struct Tracker {
+    frames: Vec<Frame>,
+    duplicates: Vec<DuplicateKey>,
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Debug)]
+enum Frame {
+    Map {
+        /// The line of each key's first occurrence.
+        keys: HashMap<String, usize>,
+        /// The key of the value being read, if a key has been read.
+        key: Option<String>,
+    },
+    /// The index of the element being read.
+    Sequence(usize),
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl Frame {
+    fn map() -> Self {
+        Self::Map {
+            keys: HashMap::new(),
+            key: None,
+        }
+    }
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl Tracker {
+    fn start(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    fn end(&mut self) {
+        self.frames.pop();
+        self.complete(None);
+    }
+
+    /// Handles a scalar node, whose text can be used as a key.
+    fn leaf(&mut self, text: Option<String>, line: usize) {
+        let first_line = match (self.frames.last_mut(), &text) {
+            (Some(Frame::Map { keys, key: None }), Some(text)) => {
+                keys.get(text).copied().or_else(|| {
+                    keys.insert(text.clone(), line);
+                    None
+                })
+            }
+            _ => None,
+        };
+
+        if let (Some(first_line), Some(text)) = (first_line, &text) {
+            self.duplicates
+                .push(DuplicateKey::new(self.path(text), first_line, line));
+        }
+
+        self.complete(text);
+    }
+
+    /// Advances the innermost frame after a node has been read.
+    fn complete(&mut self, text: Option<String>) {
+        match self.frames.last_mut() {
+            Some(Frame::Map { key, .. }) => {
+                *key = match key {
+                    // Complex keys cannot be part of paths.
+                    None => Some(text.unwrap_or_else(|| "?".to_string())),
+                    Some(_) => None,
+                };
+            }
+            Some(Frame::Sequence(index)) => *index += 1,
+            None => {}
+        }
+    }
+
+    /// Returns the path of the given key within the innermost map.
+    fn path(&self, key: &str) -> String {
+        use std::fmt::Write;
+
+        let parents = self
+            .frames
+            .split_last()
+            .map_or(&[][..], |(_, parents)| parents);
+        let mut path = String::new();
+
+        for frame in parents {
+            match frame {
+                Frame::Map { key: Some(key), .. } => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+
+                    path.push_str(key);
+                }
+                Frame::Map { key: None, .. } => {}
+                Frame::Sequence(index) => {
+                    let _ = write!(path, "[{index}]");
+                }
+            }
+        }
+
+        if !path.is_empty() {
+            path.push('.');
+        }
+
+        path.push_str(key);
+        path
+    }
+}
@@ -0,0 +1,611 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs::read_to_string;
+use std::ops::Range;
+use std::path::Path;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::path::{Segment, join, out_of_bounds, segments};
+use crate::{Error, Format, WriteOptions};
+
+mod json;
+#[cfg(feature = "yaml-edit")]
+mod yaml;
+
+/// A JSON or YAML document which is edited in place, keeping its comments and layout.
+///
+/// Changes are spliced into the document's text, so that everything outside the changed values,
+/// such as comments, whitespace, the order of keys, anchors and aliases, remains as it was.
+/// JSON documents may contain comments and trailing commas, as commonly used by JSONC files.
+///
+/// After each change, the text is parsed again to ensure that it holds the expected content.
+/// If a change cannot be spliced in, e.g. due to unusual syntax, the closest enclosing value is rewritten instead.
+///
+/// # Examples
+/// ```
+/// use serde_rw::Document;
+///
+/// #[cfg(feature = "edit")]
+/// {
+///     const JSONC: &str = "{\n  // The person's ID.\n  \"id\": 1337,\n  \"name\": \"John Doe\"\n}\n";
+///
+///     let mut document = Document::new(serde_rw::Format::Json, JSONC).unwrap();
+///     document.set("id", 42).unwrap();
+///     document.set("address.city", "Springfield").unwrap();
+///     assert_eq!(
+///         document.as_str(),
+///         "{\n  // The person's ID.\n  \"id\": 42,\n  \"name\": \"John Doe\",\n  \"address\": {\n    \"city\": \"Springfield\"\n  }\n}\n"
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Document {
+    format: Format,
+    text: String,
+}
+
+impl Document {
+    /// Parses a document of the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnsupportedFormat`] if the format is neither JSON nor, with the `yaml-edit` feature, YAML
+    /// and an [`Error`] if the text cannot be parsed.
+    pub fn new(format: Format, text: impl Into<String>) -> crate::Result<Self> {
+        let text = text.into();
+        syntax(format)?.parse(&text)?;
+        Ok(Self { format, text })
+    }
+
+    /// Reads a document from a file.
+    ///
+    /// The format is determined by the file extension, see [`Format::from_extension`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the format is not supported or the file cannot be read or parsed.
+    pub fn from_file(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        let filename = filename.as_ref();
+        let extension = filename
+            .extension()
+            .ok_or(Error::NoFileExtensionsSpecified)?;
+        let format = Format::from_extension(extension)
+            .ok_or_else(|| Error::UnsupportedFileExtension(extension.to_os_string()))?;
+        Self::new(format, read_to_string(filename)?)
+    }
+
+    /// Returns the format of the document.
+    #[must_use]
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the text of the document.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Deserializes an object from the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the deserialization fails.
+    pub fn get<T>(&self) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let (value, _) = syntax(self.format)?.parse(&self.text)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Sets the value at the given path.
+    ///
+    /// Paths consist of map keys separated by dots and sequence indices in brackets, e.g. `servers[0].host`.
+    /// Missing maps along the path are created and indexing one past the end of a sequence appends to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidPath`] if the path is malformed or a sequence index is out of bounds
+    /// and an [`Error`] if the value cannot be serialized.
+    pub fn set<T>(&mut self, path: &str, value: T) -> crate::Result<()>
+    where
+        T: Serialize,
+    {
+        self.apply(&segments(path)?, Some(&serde_json::to_value(value)?))
+    }
+
+    /// Removes the value at the given path and returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidPath`] if the path is malformed.
+    pub fn remove(&mut self, path: &str) -> crate::Result<bool> {
+        let text = self.text.clone();
+        self.apply(&segments(path)?, None)?;
+        Ok(self.text != text)
+    }
+
+    /// Deserializes an object from the document, applies the given function to it
+    /// and changes only the values whose serialization differs afterwards.
+    ///
+    /// Keys unknown to the object are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the object cannot be deserialized or serialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Document, Format};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "yaml-edit")]
+    /// {
+    ///     const YAML: &str = "# A person.\nid: 1337 # unique\nname: John Doe\n";
+    ///
+    ///     let mut document = Document::new(Format::Yaml, YAML).unwrap();
+    ///     document.update(|person: &mut Person| person.name = "Jane Doe".to_string()).unwrap();
+    ///     assert_eq!(document.as_str(), "# A person.\nid: 1337 # unique\nname: Jane Doe\n");
+    /// }
+    /// ```
+    pub fn update<T, F, R>(&mut self, function: F) -> crate::Result<R>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut object: T = self.get()?;
+        let old = serde_json::to_value(&object)?;
+        let result = function(&mut object);
+        let new = serde_json::to_value(&object)?;
+        let mut changes = Vec::new();
+        diff(&mut Vec::new(), &old, &new, &mut changes);
+
+        for (path, value) in changes {
+            self.apply(&path, value)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the document to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be written.
+    pub fn write_to_file(&self, filename: impl AsRef<Path>) -> crate::Result<()> {
+        self.write_to_file_with(filename, &WriteOptions::default())
+    }
+
+    /// Writes the document to a file using the given options.
+    ///
+    /// Options concerning serialization, such as prettifying, do not apply, since the text is written as is.
+    /// If [`WriteOptions::if_changed`] is set, the file is only written if its text differs from the document's.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be written.
+    pub fn write_to_file_with(
+        &self,
+        filename: impl AsRef<Path>,
+        options: &WriteOptions,
+    ) -> crate::Result<()> {
+        let filename = filename.as_ref();
+
+        if options.get_if_changed().is_some()
+            && read_to_string(filename).is_ok_and(|text| text == self.text)
+        {
+            return Ok(());
+        }
+
+        crate::to_file::write_bytes(filename, self.text.as_bytes(), options)
+    }
+
+    /// Sets the value at the given path or removes it, if no value is given.
+    fn apply(&mut self, path: &[Segment<'_>], value: Option<&Value>) -> crate::Result<()> {
+        let syntax = syntax(self.format)?;
+        let (current, root) = syntax.parse(&self.text)?;
+        let mut expected = current.clone();
+        edit(&mut expected, path, value)?;
+
+        if expected != current {
+            self.text = splice_verified(syntax, &self.text, &root, path, value, &expected)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Document {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// The syntax of a format, which locates values within texts and renders changes.
+trait Syntax {
+    /// Parses a text into its value and syntax tree.
+    fn parse(&self, text: &str) -> crate::Result<(Value, Node)>;
+
+    /// Renders a whole document in the style of the given text.
+    fn render(&self, text: &str, value: &Value) -> crate::Result<String>;
+
+    /// Replaces a node, which is the root if it has no parent.
+    fn replace(
+        &self,
+        text: &str,
+        parent: Option<(&Node, &Entry)>,
+        node: &Node,
+        value: &Value,
+    ) -> crate::Result<Vec<Splice>>;
+
+    /// Appends an entry to a map or sequence.
+    fn insert(
+        &self,
+        text: &str,
+        parent: Option<(&Node, &Entry)>,
+        collection: &Node,
+        key: Option<&str>,
+        value: &Value,
+    ) -> crate::Result<Vec<Splice>>;
+
+    /// Removes an entry from a map or sequence holding other entries as well.
+    fn remove(&self, text: &str, collection: &Node, index: usize) -> Vec<Splice>;
+}
+
+/// Updates an object stored in a file of the given format in place, see [`Document::update`].
+pub fn update_file<T, F, R>(
+    filename: &Path,
+    format: Format,
+    options: &WriteOptions,
+    function: F,
+) -> crate::Result<R>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&mut T) -> R,
+{
+    let mut document = Document::new(format, read_to_string(filename)?)?;
+    let result = document.update(function)?;
+    document.write_to_file_with(filename, options)?;
+    Ok(result)
+}
+
+/// Replaces a range of a text with another text.
+type Splice = (Range<usize>, String);
+
+/// A value within a text.
+#[derive(Debug)]
+struct Node {
+    /// The range of the value within the text.
+    span: Range<usize>,
+    kind: Kind,
+    /// Whether the value is written in flow style, i.e. within brackets, rather than in block style.
+    #[cfg_attr(not(feature = "yaml-edit"), allow(dead_code))]
+    flow: bool,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Leaf,
+    Map(Vec<Entry>),
+    Sequence(Vec<Entry>),
+}
+
+/// An entry of a map or sequence.
+#[derive(Debug)]
+struct Entry {
+    /// The key, unless the entry belongs to a sequence.
+    key: Option<String>,
+    /// The start of the entry, i.e. of its key or sequence indicator, within the text.
+    start: usize,
+    /// The end of the key and separator or of the sequence indicator, i.e. where the value may start.
+    #[cfg_attr(not(feature = "yaml-edit"), allow(dead_code))]
+    indicator: usize,
+    node: Node,
+}
+
+impl Node {
+    fn entries(&self) -> &[Entry] {
+        match &self.kind {
+            Kind::Leaf => &[],
+            Kind::Map(entries) | Kind::Sequence(entries) => entries,
+        }
+    }
+}
+
+fn syntax(format: Format) -> crate::Result<&'static dyn Syntax> {
+    match format {
+        Format::Json => Ok(&json::Json),
+        #[cfg(feature = "yaml-edit")]
+        Format::Yaml => Ok(&yaml::Yaml),
+        #[allow(unreachable_patterns)]
+        format => Err(Error::UnsupportedFormat(format)),
+    }
+}
+
+/// Splices a change into a text and ensures that the result holds the expected value.
+///
+/// Otherwise, the closest enclosing value is replaced, up to the whole document.
+fn splice_verified(
+    syntax: &dyn Syntax,
+    text: &str,
+    root: &Node,
+    path: &[Segment<'_>],
+    value: Option<&Value>,
+    expected: &Value,
+) -> crate::Result<String> {
+    let Some((_, parent)) = path.split_last() else {
+        return syntax.render(text, expected);
+    };
+    let changed = splice(text, plan(syntax, text, root, path, value, expected)?);
+
+    if syntax
+        .parse(&changed)
+        .is_ok_and(|(value, _)| value == *expected)
+    {
+        Ok(changed)
+    } else {
+        splice_verified(syntax, text, root, parent, get(expected, parent), expected)
+    }
+}
+
+/// Determines the splices that set the value at the given path or remove it, if no value is given.
+fn plan(
+    syntax: &dyn Syntax,
+    text: &str,
+    root: &Node,
+    path: &[Segment<'_>],
+    value: Option<&Value>,
+    expected: &Value,
+) -> crate::Result<Vec<Splice>> {
+    let mut parent = None;
+    let mut node = root;
+
+    for (depth, segment) in path.iter().enumerate() {
+        let parents = path.get(..depth).unwrap_or_default();
+        let index = match &node.kind {
+            Kind::Leaf => None,
+            Kind::Map(entries) => {
+                let key = segment.key();
+                entries
+                    .iter()
+                    .position(|entry| entry.key.as_deref() == Some(&key))
+            }
+            Kind::Sequence(entries) => segment.index().filter(|index| *index < entries.len()),
+        };
+        let Some((index, entry)) =
+            index.and_then(|index| Some((index, node.entries().get(index)?)))
+        else {
+            // Append the missing entry or replace the node, if it is not the expected kind of collection.
+            let value = get(expected, path.get(..=depth).unwrap_or_default());
+            return match (&node.kind, get(expected, parents), value) {
+                (Kind::Map(_), Some(Value::Object(_)), Some(value)) => {
+                    syntax.insert(text, parent, node, Some(&segment.key()), value)
+                }
+                (Kind::Sequence(entries), Some(Value::Array(_)), Some(value))
+                    if segment.index() == Some(entries.len()) =>
+                {
+                    syntax.insert(text, parent, node, None, value)
+                }
+                (_, Some(value), _) => syntax.replace(text, parent, node, value),
+                (_, None, _) => Ok(vec![(0..text.len(), syntax.render(text, expected)?)]),
+            };
+        };
+
+        if depth + 1 < path.len() {
+            parent = Some((node, entry));
+            node = &entry.node;
+            continue;
+        }
+
+        return match (value, get(expected, parents)) {
+            (Some(value), _) => syntax.replace(text, Some((node, entry)), &entry.node, value),
+            (None, _) if node.entries().len() > 1 => Ok(syntax.remove(text, node, index)),
+            // Removing the only entry leaves an empty collection, which is written in flow style.
+            (None, Some(value)) => syntax.replace(text, parent, node, value),
+            (None, None) => Ok(vec![(0..text.len(), syntax.render(text, expected)?)]),
+        };
+    }
+
+    Ok(vec![(0..text.len(), syntax.render(text, expected)?)])
+}
+
+/// Applies splices, which must not overlap, to a text.
+///
+/// Insertions at the same position end up in the order of the given splices.
+fn splice(text: &str, mut splices: Vec<Splice>) -> String {
+    let mut text = text.to_string();
+    splices.reverse();
+    splices.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+    for (range, replacement) in splices {
+        text.replace_range(range, &replacement);
+    }
+
+    text
+}
+
+/// Returns the value at the given path, if it exists.
+fn get<'value>(value: &'value Value, path: &[Segment<'_>]) -> Option<&'value Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Array(array) => array.get(segment.index()?),
+        Value::Object(object) => object.get(&segment.key()),
+        _ => None,
+    })
+}
+
+/// Sets the value at the given path or removes it, if no value is given.
+fn edit(target: &mut Value, path: &[Segment<'_>], value: Option<&Value>) -> crate::Result<()> {
+    let Some((last, parents)) = path.split_last() else {
+        *target = value.cloned().unwrap_or_default();
+        return Ok(());
+    };
+    let mut target = target;
+
+    for segment in parents {
+        target = match value {
+            Some(_) => child(target, *segment, path)?,
+            None => match get_mut(target, *segment) {
+                Some(target) => target,
+                None => return Ok(()),
+            },
+        };
+    }
+
+    match (target, value, last.index()) {
+        (Value::Array(array), Some(value), Some(index)) if index < array.len() => {
+            array[index] = value.clone();
+        }
+        (Value::Array(array), Some(value), Some(index)) if index == array.len() => {
+            array.push(value.clone());
+        }
+        (Value::Array(_), Some(_), Some(index)) => return Err(out_of_bounds(&join(path), index)),
+        (target, Some(value), _) => {
+            as_object(target).insert(last.key(), value.clone());
+        }
+        (Value::Array(array), None, Some(index)) if index < array.len() => {
+            array.remove(index);
+        }
+        (Value::Object(object), None, _) => {
+            object.remove(&last.key());
+        }
+        (_, None, _) => {}
+    }
+
+    Ok(())
+}
+
+/// Returns the child of the given value at the segment, creating it if necessary.
+fn child<'value>(
+    value: &'value mut Value,
+    segment: Segment<'_>,
+    path: &[Segment<'_>],
+) -> crate::Result<&'value mut Value> {
+    match (value, segment.index()) {
+        (Value::Array(array), Some(index)) => {
+            if index == array.len() {
+                array.push(Value::Null);
+            }
+
+            array
+                .get_mut(index)
+                .ok_or_else(|| out_of_bounds(&join(path), index))
+        }
+        (value, _) => Ok(as_object(value).entry(segment.key()).or_insert(Value::Null)),
+    }
+}
+
+fn get_mut<'value>(value: &'value mut Value, segment: Segment<'_>) -> Option<&'value mut Value> {
+    match value {
+        Value::Array(array) => array.get_mut(segment.index()?),
+        Value::Object(object) => object.get_mut(&segment.key()),
+        _ => None,
+    }
+}
+
+/// Returns the object of the given value, replacing the value by an empty object if it is not an object.
+fn as_object(value: &mut Value) -> &mut serde_json::Map<String, Value> {
+    if !value.is_object() {
+        *value = Value::Object(serde_json::Map::new());
+    }
+
+    match value {
+        Value::Object(object) => object,
+        _ => unreachable!("value has been converted into an object"),
+    }
+}
+
+/// Determines the changes that turn the old value into the new one.
+///
+/// Changes are paths along with the value to set or [`None`] to remove the value at the path.
+fn diff<'value>(
+    path: &mut Vec<Segment<'value>>,
+    old: &'value Value,
+    new: &'value Value,
+    changes: &mut Vec<(Vec<Segment<'value>>, Option<&'value Value>)>,
+) {
+    match (old, new) {
+        _ if old == new => {}
+        (Value::Object(old), Value::Object(new)) if !new.is_empty() => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                path.push(Segment::Key(key));
+                changes.push((path.clone(), None));
+                path.pop();
+            }
+
+            for (key, new) in new {
+                path.push(Segment::Key(key));
+
+                match old.get(key) {
+                    Some(old) => diff(path, old, new, changes),
+                    None => changes.push((path.clone(), Some(new))),
+                }
+
+                path.pop();
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if !new.is_empty() => {
+            for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                path.push(Segment::Index(index));
+                diff(path, old, new, changes);
+                path.pop();
+            }
+
+            for index in (new.len()..old.len()).rev() {
+                path.push(Segment::Index(index));
+                changes.push((path.clone(), None));
+                path.pop();
+            }
+
+            for (index, new) in new.iter().enumerate().skip(old.len()) {
+                path.push(Segment::Index(index));
+                changes.push((path.clone(), Some(new)));
+                path.pop();
+            }
+        }
+        _ => changes.push((path.clone(), Some(new))),
+    }
+}
+
+/// Returns the start of the line containing the given position.
+fn line_start(text: &str, position: usize) -> usize {
+    text.get(..position)
+        .and_then(|before| before.rfind('\n'))
+        .map_or(0, |newline| newline + 1)
+}
+
+/// Returns the end of the line containing the given position, excluding the line break.
+fn line_end(text: &str, position: usize) -> usize {
+    text.get(position..)
+        .and_then(|after| after.find('\n'))
+        .map_or(text.len(), |newline| position + newline)
+}
+
+/// Returns the indentation of the line containing the given position.
+fn indentation(text: &str, position: usize) -> &str {
+    let start = line_start(text, position);
+    let line = text
+        .get(start..line_end(text, position))
+        .unwrap_or_default();
+    line.get(..line.len() - line.trim_start_matches([' ', '\t']).len())
+        .unwrap_or_default()
+}
+
+/// Returns whether only whitespace precedes the given position within its line.
+fn starts_line(text: &str, position: usize) -> bool {
+    text.get(line_start(text, position)..position)
+        .is_some_and(|before| before.trim().is_empty())
+}
+
+/// Indents all but the first line of a text.
+fn indent_continuation(text: &str, indentation: &str) -> String {
+    text.replace('\n', &format!("\n{indentation}"))
+}
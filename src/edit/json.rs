@@ -0,0 +1,396 @@
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::ser::PrettyFormatter;
+
+use super::{
+    Entry, Kind, Node, Splice, Syntax, indent_continuation, indentation, line_end, line_start,
+    starts_line,
+};
+
+/// The indentation used if the text does not indicate any.
+const DEFAULT_INDENT: &str = "  ";
+
+/// JSON, optionally with comments and trailing commas.
+pub struct Json;
+
+impl Syntax for Json {
+    fn parse(&self, text: &str) -> crate::Result<(Value, Node)> {
+        let stripped = strip(text);
+        let value = serde_json::from_str(&stripped)?;
+        let mut parser = Parser {
+            text: &stripped,
+            position: 0,
+        };
+        Ok((value, parser.node()))
+    }
+
+    fn render(&self, text: &str, value: &Value) -> crate::Result<String> {
+        let mut rendered = pretty(value, indent_unit(text))?;
+        rendered.push('\n');
+        Ok(rendered)
+    }
+
+    fn replace(
+        &self,
+        text: &str,
+        parent: Option<(&Node, &Entry)>,
+        node: &Node,
+        value: &Value,
+    ) -> crate::Result<Vec<Splice>> {
+        let rendered = if parent.is_none_or(|(collection, _)| spans_lines(text, collection)) {
+            indent_continuation(
+                &pretty(value, indent_unit(text))?,
+                indentation(text, node.span.start),
+            )
+        } else {
+            inline(value)?
+        };
+        Ok(vec![(node.span.clone(), rendered)])
+    }
+
+    fn insert(
+        &self,
+        text: &str,
+        parent: Option<(&Node, &Entry)>,
+        collection: &Node,
+        key: Option<&str>,
+        value: &Value,
+    ) -> crate::Result<Vec<Splice>> {
+        let unit = indent_unit(text);
+        let prefix = key
+            .map(serde_json::to_string)
+            .transpose()?
+            .map_or_else(String::new, |key| format!("{key}: "));
+        let (start, end) = (collection.span.start, collection.span.end);
+
+        let Some(last) = collection.entries().last() else {
+            let inner = start + 1..end.saturating_sub(1).max(start + 1);
+
+            if parent.is_none_or(|(parent, _)| spans_lines(text, parent)) {
+                let indent = indentation(text, start);
+                let inner_indent = format!("{indent}{unit}");
+                let entry = indent_continuation(&pretty(value, unit)?, &inner_indent);
+                return Ok(vec![(
+                    inner,
+                    format!("\n{inner_indent}{prefix}{entry}\n{indent}"),
+                )]);
+            }
+
+            return Ok(vec![(inner, format!("{prefix}{}", inline(value)?))]);
+        };
+
+        let position = last.node.span.end;
+        let stripped = strip(text);
+        let rest = stripped
+            .get(position..line_end(text, position))
+            .unwrap_or_default();
+
+        if spans_lines(text, collection)
+            && starts_line(text, last.start)
+            && rest.trim_matches([' ', '\t', '\r', ',']).is_empty()
+        {
+            let indent = indentation(text, last.start);
+            let entry = indent_continuation(&pretty(value, unit)?, indent);
+            let line_end = line_end(text, position);
+
+            // Retain the style of trailing commas and keep comments after the last entry on its line.
+            return Ok(if trailing_comma(text, position).is_some() {
+                vec![(line_end..line_end, format!("\n{indent}{prefix}{entry},"))]
+            } else {
+                vec![
+                    (position..position, ",".to_string()),
+                    (line_end..line_end, format!("\n{indent}{prefix}{entry}")),
+                ]
+            });
+        }
+
+        Ok(vec![(
+            position..position,
+            format!(", {prefix}{}", inline(value)?),
+        )])
+    }
+
+    fn remove(&self, text: &str, collection: &Node, index: usize) -> Vec<Splice> {
+        let entries = collection.entries();
+        let (Some(entry), previous, next) = (
+            entries.get(index),
+            index.checked_sub(1).and_then(|index| entries.get(index)),
+            entries.get(index + 1),
+        ) else {
+            return Vec::new();
+        };
+        let stripped = strip(text);
+        let end = entry.node.span.end;
+        let rest = stripped.get(end..line_end(text, end)).unwrap_or_default();
+
+        if spans_lines(text, collection)
+            && starts_line(text, entry.start)
+            && rest.trim_matches([' ', '\t', '\r', ',']).is_empty()
+        {
+            let line = line_start(text, entry.start)..(line_end(text, end) + 1).min(text.len());
+
+            return match (next, previous) {
+                (None, Some(previous)) if trailing_comma(text, end).is_none() => {
+                    // The previous entry becomes the last one, so its separating comma is dropped.
+                    trailing_comma(&stripped, previous.node.span.end).map_or_else(
+                        || vec![(line.clone(), String::new())],
+                        |comma| {
+                            vec![
+                                (comma..comma + 1, String::new()),
+                                (line.clone(), String::new()),
+                            ]
+                        },
+                    )
+                }
+                _ => vec![(line, String::new())],
+            };
+        }
+
+        match (next, previous) {
+            (Some(next), _) => vec![(entry.start..next.start, String::new())],
+            (None, Some(previous)) => vec![(previous.node.span.end..end, String::new())],
+            (None, None) => vec![(entry.start..end, String::new())],
+        }
+    }
+}
+
+/// Parses the syntax tree of JSON text without comments, which has already been validated.
+struct Parser<'text> {
+    text: &'text str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn node(&mut self) -> Node {
+        self.skip_whitespace();
+        let start = self.position;
+        let kind = match self.peek() {
+            Some(b'{') => Kind::Map(self.entries(b'}', true)),
+            Some(b'[') => Kind::Sequence(self.entries(b']', false)),
+            Some(b'"') => {
+                self.string();
+                Kind::Leaf
+            }
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|byte| !b",:]} \t\r\n".contains(&byte))
+                {
+                    self.position += 1;
+                }
+
+                Kind::Leaf
+            }
+        };
+        Node {
+            span: start..self.position,
+            kind,
+            flow: true,
+        }
+    }
+
+    fn entries(&mut self, close: u8, keyed: bool) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        self.position += 1;
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                None => break,
+                Some(byte) if byte == close => {
+                    self.position += 1;
+                    break;
+                }
+                Some(b',') => self.position += 1,
+                Some(_) if keyed => {
+                    let start = self.position;
+                    self.string();
+                    let key = self
+                        .text
+                        .get(start..self.position)
+                        .and_then(|key| serde_json::from_str(key).ok());
+                    self.skip_whitespace();
+
+                    if self.peek() == Some(b':') {
+                        self.position += 1;
+                    }
+
+                    let indicator = self.position;
+                    entries.push(Entry {
+                        key,
+                        start,
+                        indicator,
+                        node: self.node(),
+                    });
+                }
+                Some(_) => {
+                    let node = self.node();
+                    entries.push(Entry {
+                        key: None,
+                        start: node.span.start,
+                        indicator: node.span.start,
+                        node,
+                    });
+                }
+            }
+        }
+
+        entries
+    }
+
+    fn string(&mut self) {
+        let mut escaped = false;
+        self.position += 1;
+
+        while let Some(byte) = self.peek() {
+            self.position += 1;
+
+            match byte {
+                b'"' if !escaped => break,
+                b'\\' => escaped = !escaped,
+                _ => escaped = false,
+            }
+        }
+    }
+}
+
+/// Replaces comments and trailing commas with whitespace, retaining the positions of everything else.
+fn strip(text: &str) -> String {
+    let mut bytes = text.as_bytes().to_vec();
+    let mut index = 0;
+    let mut commas = Vec::new();
+
+    while let Some(&byte) = bytes.get(index) {
+        match (byte, bytes.get(index + 1)) {
+            (b'"', _) => {
+                let mut escaped = false;
+
+                while let Some(&byte) = bytes.get(index + 1) {
+                    index += 1;
+
+                    match byte {
+                        b'"' if !escaped => break,
+                        b'\\' => escaped = !escaped,
+                        _ => escaped = false,
+                    }
+                }
+            }
+            (b'/', Some(b'/')) => {
+                while bytes.get(index).is_some_and(|byte| *byte != b'\n') {
+                    bytes[index] = b' ';
+                    index += 1;
+                }
+
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                let end = text
+                    .get(index + 2..)
+                    .and_then(|rest| rest.find("*/"))
+                    .map_or(bytes.len(), |end| index + end + 4);
+
+                for byte in bytes.iter_mut().take(end).skip(index) {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+
+                index = end;
+                continue;
+            }
+            (b',', _) => commas.push(index),
+            (b']' | b'}', _) => {
+                // A comma directly followed by a closing bracket is a trailing comma.
+                if let Some(comma) = commas.pop()
+                    && bytes
+                        .get(comma + 1..index)
+                        .is_some_and(|between| between.iter().all(u8::is_ascii_whitespace))
+                {
+                    bytes[comma] = b' ';
+                }
+            }
+            _ => {}
+        }
+
+        if !byte.is_ascii_whitespace() && byte != b',' {
+            commas.clear();
+        }
+
+        index += 1;
+    }
+
+    // Only ASCII characters have been replaced, so the text remains valid UTF-8.
+    String::from_utf8(bytes).unwrap_or_else(|_| text.to_string())
+}
+
+/// Returns the position of the comma following the given position, if only whitespace lies in between.
+fn trailing_comma(text: &str, position: usize) -> Option<usize> {
+    let rest = text.get(position..)?;
+    let comma = position + rest.len() - rest.trim_start().len();
+    (text.as_bytes().get(comma) == Some(&b',')).then_some(comma)
+}
+
+/// Returns the indentation per level of the given text.
+fn indent_unit(text: &str) -> &str {
+    text.lines()
+        .map(|line| {
+            line.get(..line.len() - line.trim_start_matches([' ', '\t']).len())
+                .unwrap_or_default()
+        })
+        .find(|indent| !indent.is_empty())
+        .unwrap_or(DEFAULT_INDENT)
+}
+
+/// Returns whether a node spans multiple lines.
+fn spans_lines(text: &str, node: &Node) -> bool {
+    text.get(node.span.clone())
+        .is_some_and(|text| text.contains('\n'))
+}
+
+/// Renders a value across multiple lines.
+fn pretty(value: &Value, indent: &str) -> crate::Result<String> {
+    let mut bytes = Vec::new();
+    value.serialize(&mut serde_json::Serializer::with_formatter(
+        &mut bytes,
+        PrettyFormatter::with_indent(indent.as_bytes()),
+    ))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Renders a value on a single line.
+fn inline(value: &Value) -> crate::Result<String> {
+    Ok(match value {
+        Value::Array(array) => format!(
+            "[{}]",
+            array
+                .iter()
+                .map(inline)
+                .collect::<crate::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        Value::Object(object) => format!(
+            "{{{}}}",
+            object
+                .iter()
+                .map(|(key, value)| Ok(format!(
+                    "{}: {}",
+                    serde_json::to_string(key)?,
+                    inline(value)?
+                )))
+                .collect::<crate::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        value => serde_json::to_string(value)?,
+    })
+}
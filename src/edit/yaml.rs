@@ -0,0 +1,488 @@
+use serde_json::Value;
+use yaml_rust2::Event;
+use yaml_rust2::parser::{MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+use super::{
+    Entry, Kind, Node, Splice, Syntax, indent_continuation, line_end, line_start, starts_line,
+};
+
+/// The characters which cannot appear unquoted within flow collections.
+const FLOW_INDICATORS: [char; 5] = [',', '[', ']', '{', '}'];
+
+/// The maximum number of properties, i.e. an anchor and a tag, preceding a value.
+const MAX_PROPERTIES: usize = 3;
+
+/// A key along with its start and end within the text.
+type Key = (String, usize, usize);
+
+/// YAML, of which only the first document is considered.
+pub struct Yaml;
+
+impl Syntax for Yaml {
+    fn parse(&self, text: &str) -> crate::Result<(Value, Node)> {
+        let value = serde_yaml::from_str(text)?;
+        let mut builder = Builder {
+            text,
+            offsets: text
+                .char_indices()
+                .map(|(offset, _)| offset)
+                .chain([text.len()])
+                .collect(),
+            stack: Vec::new(),
+            root: None,
+        };
+        Parser::new_from_str(text)
+            .load(&mut builder, false)
+            .map_err(|error| crate::Error::Serde(Box::new(error)))?;
+        let root = builder.root.unwrap_or(Node {
+            span: 0..0,
+            kind: Kind::Leaf,
+            flow: false,
+        });
+        Ok((value, root))
+    }
+
+    fn render(&self, _: &str, value: &Value) -> crate::Result<String> {
+        Ok(serde_yaml::to_string(value)?)
+    }
+
+    fn replace(
+        &self,
+        text: &str,
+        parent: Option<(&Node, &Entry)>,
+        node: &Node,
+        value: &Value,
+    ) -> crate::Result<Vec<Splice>> {
+        let Some((collection, entry)) = parent else {
+            return Ok(vec![(node.span.clone(), block(value)?)]);
+        };
+
+        if collection.flow {
+            return Ok(vec![(node.span.clone(), flow(value)?)]);
+        }
+
+        let column = entry.start - line_start(text, entry.start);
+        let line_end = line_end(text, entry.indicator);
+        let is_map = matches!(collection.kind, Kind::Map(_));
+
+        let start = if node.span.start <= line_end {
+            let before = text.get(..node.span.start).unwrap_or_default();
+            before
+                .trim_end_matches([' ', '\t'])
+                .len()
+                .max(entry.indicator)
+        } else if text
+            .get(entry.indicator..line_end)
+            .is_some_and(|rest| rest.trim().is_empty())
+        {
+            entry.indicator
+        } else {
+            // Comments or properties following the key or indicator are kept on their line.
+            let indent = " ".repeat(column + 2);
+            let rendered = indent_continuation(&block(value)?, &indent);
+            return Ok(vec![(
+                line_end..node.span.end,
+                format!("\n{indent}{rendered}"),
+            )]);
+        };
+        Ok(vec![(
+            start..node.span.end,
+            value_text(value, column, is_map)?,
+        )])
+    }
+
+    fn insert(
+        &self,
+        text: &str,
+        _: Option<(&Node, &Entry)>,
+        collection: &Node,
+        key: Option<&str>,
+        value: &Value,
+    ) -> crate::Result<Vec<Splice>> {
+        let entries = collection.entries();
+        let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+            // Empty collections are always written in flow style.
+            let inner = collection.span.start + 1..collection.span.end.saturating_sub(1);
+            let prefix = key
+                .map(render_key)
+                .transpose()?
+                .map_or_else(String::new, |key| format!("{key}: "));
+            return Ok(vec![(inner, format!("{prefix}{}", flow(value)?))]);
+        };
+
+        if collection.flow {
+            let prefix = key
+                .map(render_key)
+                .transpose()?
+                .map_or_else(String::new, |key| format!("{key}: "));
+            let end = last.node.span.end;
+            return Ok(vec![(end..end, format!(", {prefix}{}", flow(value)?))]);
+        }
+
+        let column = first.start - line_start(text, first.start);
+        let indicator = key.map_or_else(
+            || Ok("-".to_string()),
+            |key| render_key(key).map(|key| format!("{key}:")),
+        )?;
+        let position = line_end(text, last.node.span.end);
+        Ok(vec![(
+            position..position,
+            format!(
+                "\n{}{indicator}{}",
+                " ".repeat(column),
+                value_text(value, column, key.is_some())?
+            ),
+        )])
+    }
+
+    fn remove(&self, text: &str, collection: &Node, index: usize) -> Vec<Splice> {
+        let entries = collection.entries();
+        let (Some(entry), previous, next) = (
+            entries.get(index),
+            index.checked_sub(1).and_then(|index| entries.get(index)),
+            entries.get(index + 1),
+        ) else {
+            return Vec::new();
+        };
+        let end = entry.node.span.end;
+
+        if collection.flow {
+            return match (next, previous) {
+                (Some(next), _) => vec![(entry.start..next.start, String::new())],
+                (None, Some(previous)) => vec![(previous.node.span.end..end, String::new())],
+                (None, None) => vec![(entry.start..end, String::new())],
+            };
+        }
+
+        let next_line = (line_end(text, end) + 1).min(text.len());
+
+        if starts_line(text, entry.start) {
+            vec![(line_start(text, entry.start)..next_line, String::new())]
+        } else {
+            // The entry shares its line with a sequence indicator, which the following entry takes over.
+            let rest = text.get(next_line..).unwrap_or_default();
+            let next_start = next_line + rest.len() - rest.trim_start_matches(' ').len();
+            vec![(entry.start..next_start, String::new())]
+        }
+    }
+}
+
+/// Builds the syntax tree of the first document from the parser's events.
+struct Builder<'text> {
+    text: &'text str,
+    /// The byte offset of each character, as the parser's markers count characters.
+    offsets: Vec<usize>,
+    /// The collections being built along with the key of the entry being read, if any.
+    stack: Vec<(Node, Option<Key>)>,
+    root: Option<Node>,
+}
+
+impl MarkedEventReceiver for Builder<'_> {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if self.root.is_some() {
+            return;
+        }
+
+        let position = self
+            .offsets
+            .get(mark.index())
+            .copied()
+            .unwrap_or(self.text.len());
+
+        match event {
+            Event::Scalar(value, style, ..) => {
+                let span = self.scalar_span(&value, style, position);
+                self.leaf(value, span.start, span.end);
+            }
+            Event::Alias(_) => {
+                let rest = self.text.get(position..).unwrap_or_default();
+                let end = rest
+                    .find(|char: char| char.is_whitespace() || FLOW_INDICATORS.contains(&char))
+                    .map_or(self.text.len(), |end| position + end);
+                self.leaf(String::new(), position, end);
+            }
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                let kind = if matches!(event, Event::MappingStart(..)) {
+                    Kind::Map(Vec::new())
+                } else {
+                    Kind::Sequence(Vec::new())
+                };
+                let flow = self
+                    .text
+                    .as_bytes()
+                    .get(position)
+                    .is_some_and(|byte| matches!(byte, b'{' | b'['));
+                let node = Node {
+                    span: position..position,
+                    kind,
+                    flow,
+                };
+                self.stack.push((node, None));
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                if let Some((mut node, _)) = self.stack.pop() {
+                    if node.flow {
+                        node.span.end = position + 1;
+                    } else if let (Some(first), Some(last)) =
+                        (node.entries().first(), node.entries().last())
+                    {
+                        node.span = first.start..last.node.span.end;
+                    }
+
+                    self.attach(node);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Builder<'_> {
+    /// Handles a scalar, which is either a key or a value.
+    fn leaf(&mut self, value: String, start: usize, end: usize) {
+        if let Some((
+            Node {
+                kind: Kind::Map(_), ..
+            },
+            key @ None,
+        )) = self.stack.last_mut()
+        {
+            *key = Some((value, start, end));
+            return;
+        }
+
+        self.attach(Node {
+            span: start..end,
+            kind: Kind::Leaf,
+            flow: false,
+        });
+    }
+
+    /// Adds a value to the innermost collection or makes it the root.
+    fn attach(&mut self, node: Node) {
+        let Some((collection, key)) = self.stack.last_mut() else {
+            self.root = Some(node);
+            return;
+        };
+
+        match &mut collection.kind {
+            Kind::Map(entries) => match key.take() {
+                Some((key, start, end)) => {
+                    let indicator = self
+                        .text
+                        .get(end..)
+                        .and_then(|rest| rest.find(':'))
+                        .map_or(end, |colon| end + colon + 1);
+                    entries.push(Entry {
+                        key: Some(key),
+                        start,
+                        indicator,
+                        node,
+                    });
+                }
+                // Complex keys cannot be edited.
+                None => *key = Some((String::new(), node.span.start, node.span.end)),
+            },
+            Kind::Sequence(entries) => {
+                let start = if collection.flow {
+                    node.span.start
+                } else {
+                    sequence_indicator(self.text, node.span.start)
+                };
+                entries.push(Entry {
+                    key: None,
+                    start,
+                    indicator: start + 1,
+                    node,
+                });
+            }
+            Kind::Leaf => {}
+        }
+    }
+
+    /// Returns the range of a scalar starting at the given position.
+    fn scalar_span(
+        &self,
+        value: &str,
+        style: TScalarStyle,
+        position: usize,
+    ) -> std::ops::Range<usize> {
+        let text = self.text;
+        let rest = text.get(position..).unwrap_or_default();
+
+        match style {
+            TScalarStyle::DoubleQuoted | TScalarStyle::SingleQuoted => {
+                position..position + quoted_len(rest, style == TScalarStyle::DoubleQuoted)
+            }
+            TScalarStyle::Literal | TScalarStyle::Folded => {
+                let start = text
+                    .get(..position)
+                    .and_then(|before| before.rfind(['|', '>']))
+                    .unwrap_or(position);
+                start..block_scalar_end(text, position)
+            }
+            TScalarStyle::Plain if value.is_empty() => {
+                // Empty values are located right after the separator of their key.
+                let position = match self.stack.last() {
+                    Some((_, Some((_, _, end)))) => text
+                        .get(*end..)
+                        .and_then(|rest| rest.find(':'))
+                        .map_or(position, |colon| end + colon + 1),
+                    _ => position,
+                };
+                position..position
+            }
+            TScalarStyle::Plain if rest.starts_with(value) => position..position + value.len(),
+            TScalarStyle::Plain => {
+                let line = rest.get(..line_end(rest, 0)).unwrap_or_default();
+                let line = line.split(" #").next().unwrap_or_default();
+                position..position + line.trim_end().len()
+            }
+        }
+    }
+}
+
+/// Returns the length of a quoted scalar including its quotes.
+fn quoted_len(text: &str, double: bool) -> usize {
+    let mut chars = text.char_indices().skip(1);
+
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '\\' if double => {
+                chars.next();
+            }
+            '"' if double => return index + 1,
+            '\'' if !double => {
+                if text
+                    .get(index + 1..)
+                    .is_some_and(|rest| rest.starts_with('\''))
+                {
+                    chars.next();
+                } else {
+                    return index + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    text.len()
+}
+
+/// Returns the end of a block scalar, whose content starts at the given position.
+fn block_scalar_end(text: &str, position: usize) -> usize {
+    let indent = position - line_start(text, position);
+    let mut end = line_end(text, position);
+    let mut next = end + 1;
+
+    while let Some(line) = text
+        .get(next..line_end(text, next))
+        .filter(|_| next < text.len())
+    {
+        if !line.trim().is_empty() {
+            if line.len() - line.trim_start_matches(' ').len() < indent {
+                break;
+            }
+
+            end = next + line.len();
+        }
+
+        next += line.len() + 1;
+    }
+
+    end
+}
+
+/// Returns the position of the indicator of a block sequence's item,
+/// which may be separated from the item by whitespace and properties, i.e. anchors and tags.
+fn sequence_indicator(text: &str, item: usize) -> usize {
+    let mut before = text.get(..item).unwrap_or_default();
+
+    for _ in 0..MAX_PROPERTIES {
+        let trimmed = before.trim_end();
+
+        if trimmed.ends_with('-')
+            && trimmed
+                .get(..trimmed.len() - 1)
+                .is_some_and(|before| before.is_empty() || before.ends_with(char::is_whitespace))
+        {
+            return trimmed.len() - 1;
+        }
+
+        before = trimmed
+            .get(
+                ..trimmed
+                    .rfind(char::is_whitespace)
+                    .map_or(0, |space| space + 1),
+            )
+            .unwrap_or_default();
+    }
+
+    item
+}
+
+/// Renders a value following the key or indicator of an entry at the given column in block style.
+fn value_text(value: &Value, column: usize, is_map: bool) -> crate::Result<String> {
+    let rendered = block(value)?;
+    let indent = " ".repeat(column + 2);
+
+    Ok(match value {
+        Value::Object(object) if !object.is_empty() && is_map => {
+            format!("\n{indent}{}", indent_continuation(&rendered, &indent))
+        }
+        Value::Array(array) if !array.is_empty() && is_map => {
+            format!("\n{indent}{}", indent_continuation(&rendered, &indent))
+        }
+        Value::Object(object) if !object.is_empty() => {
+            format!(" {}", indent_continuation(&rendered, &indent))
+        }
+        Value::Array(array) if !array.is_empty() => {
+            format!(" {}", indent_continuation(&rendered, &indent))
+        }
+        _ => format!(" {}", indent_continuation(&rendered, &" ".repeat(column))),
+    })
+}
+
+/// Renders a value in block style without a trailing line break.
+fn block(value: &Value) -> crate::Result<String> {
+    Ok(serde_yaml::to_string(value)?
+        .trim_end_matches('\n')
+        .to_string())
+}
+
+/// Renders a value in flow style.
+fn flow(value: &Value) -> crate::Result<String> {
+    Ok(match value {
+        Value::Array(array) => format!(
+            "[{}]",
+            array
+                .iter()
+                .map(flow)
+                .collect::<crate::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        Value::Object(object) => format!(
+            "{{{}}}",
+            object
+                .iter()
+                .map(|(key, value)| Ok(format!("{}: {}", render_key(key)?, flow(value)?)))
+                .collect::<crate::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        value => {
+            let rendered = block(value)?;
+
+            if rendered.contains('\n') || rendered.contains(FLOW_INDICATORS) {
+                serde_json::to_string(value)?
+            } else {
+                rendered
+            }
+        }
+    })
+}
+
+/// Renders a key, quoting it if necessary.
+fn render_key(key: &str) -> crate::Result<String> {
+    flow(&Value::String(key.to_string()))
+}
@@ -0,0 +1,309 @@
+use std::env::vars_os;
+
+use serde_json::{Map, Number, Value};
+
+use crate::{Error, FromFile};
+
+const DEFAULT_SEPARATOR: &str = "__";
+
+/// Overrides values loaded from files with environment variables.
+///
+/// Environment variables starting with the given prefix are mapped onto nested fields
+/// by splitting the remainder of their names at the nesting separator.
+/// For example, with the prefix `MYAPP_` and the default separator `__`,
+/// `MYAPP_DB__PORT=5433` overrides the field `db.port`.
+/// Field names are matched case-insensitively against the fields of the file.
+/// Fields that do not exist in the file are named in lower case,
+/// e.g. `MYAPP_DB__PORT` sets the field `db.port` if the file lacks it.
+/// Numeric path segments index into existing arrays.
+///
+/// Values are parsed according to the type of the value they override:
+/// numbers and booleans are parsed as such, while arrays and objects are expected to be JSON-encoded.
+/// Values of fields that do not exist yet are parsed as JSON if possible and used as strings otherwise.
+/// If such a guessed value does not deserialize into the field, the raw string is used instead,
+/// so that e.g. `MYAPP_PASSWORD=12345` sets a string field.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{EnvOverrides, FromFile};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, Eq, PartialEq)]
+/// struct Person {
+///     id: u32,
+///     name: String,
+///     #[serde(default)]
+///     address: Option<Address>,
+/// }
+///
+/// #[derive(Debug, Deserialize, Eq, PartialEq)]
+/// struct Address {
+///     city: String,
+/// }
+///
+/// let overrides =
+///     EnvOverrides::new("MYAPP_").vars([("MYAPP_ID", "42"), ("MYAPP_ADDRESS__CITY", "Springfield")]);
+///
+/// #[cfg(feature = "toml")]
+/// {
+///     assert_eq!(
+///         Person::from_file_with_env("./tests/person.toml", &overrides).unwrap(),
+///         Person {
+///             id: 42,
+///             name: "John Doe".to_string(),
+///             address: Some(Address {
+///                 city: "Springfield".to_string(),
+///             }),
+///         }
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EnvOverrides {
+    prefix: String,
+    separator: String,
+    vars: Option<Vec<(String, String)>>,
+}
+
+impl EnvOverrides {
+    /// Creates overrides from environment variables with the given prefix.
+    #[must_use]
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            separator: DEFAULT_SEPARATOR.to_string(),
+            vars: None,
+        }
+    }
+
+    /// Sets the separator that denotes nested fields.
+    ///
+    /// Defaults to `__`.
+    #[must_use]
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Uses the given variables instead of the process' environment.
+    #[must_use]
+    pub fn vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.vars = Some(
+            vars.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Applies the overrides to the given value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidEnvVar`] if a variable's value does not match the type of the value it overrides.
+    pub fn apply(&self, value: &mut Value) -> crate::Result<()> {
+        self.apply_tracked(value).map(drop)
+    }
+
+    /// Applies the overrides to the given value and deserializes it into an object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidEnvVar`] if a variable's value cannot be applied
+    /// or the overridden field cannot be deserialized.
+    /// Returns an [`Error::Serde`] if the deserialization fails otherwise.
+    pub fn deserialize<T>(&self, mut value: Value) -> crate::Result<T>
+    where
+        T: FromFile,
+    {
+        let mut applied = self.apply_tracked(&mut value)?;
+
+        loop {
+            match serde_path_to_error::deserialize(value.clone()) {
+                Ok(object) => return Ok(object),
+                Err(error) => {
+                    let path = error.path().to_string();
+                    let Some(index) = applied
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, var)| var.overrides(&path))
+                        .max_by_key(|(_, var)| var.path.len())
+                        .map(|(index, _)| index)
+                    else {
+                        return Err(error.into_inner().into());
+                    };
+
+                    // Fall back to the raw string if the type of a new field was guessed.
+                    let Some(raw) = applied[index].guessed.take() else {
+                        return Err(Error::InvalidEnvVar {
+                            name: applied.swap_remove(index).name,
+                            source: Box::new(error.into_inner()),
+                        });
+                    };
+
+                    if let Some(target) = value.pointer_mut(&applied[index].pointer) {
+                        *target = Value::String(raw);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies the overrides and returns the applied variables.
+    fn apply_tracked(&self, value: &mut Value) -> crate::Result<Vec<Applied>> {
+        let mut vars: Vec<(String, String)> = self.vars.clone().unwrap_or_else(|| {
+            vars_os()
+                .filter_map(|(key, value)| {
+                    Some((key.into_string().ok()?, value.into_string().ok()?))
+                })
+                .collect()
+        });
+        vars.sort();
+
+        let mut applied = Vec::new();
+
+        for (name, raw) in vars {
+            let Some(path) = name.strip_prefix(&self.prefix) else {
+                continue;
+            };
+            let segments: Vec<&str> = path
+                .split(&self.separator)
+                .filter(|segment| !segment.is_empty())
+                .collect();
+
+            if segments.is_empty() {
+                continue;
+            }
+
+            let (target, location) = locate(value, &segments);
+            let parsed = parse(&raw, target).map_err(|message| Error::InvalidEnvVar {
+                name: name.clone(),
+                source: message.into(),
+            })?;
+            let guessed = (target.is_null() && !parsed.is_string()).then_some(raw);
+            *target = parsed;
+            applied.push(Applied {
+                name,
+                path: location.path,
+                pointer: location.pointer,
+                guessed,
+            });
+        }
+
+        Ok(applied)
+    }
+}
+
+/// A variable that has been applied onto a value.
+struct Applied {
+    name: String,
+    path: String,
+    pointer: String,
+    /// The raw value of the variable, if the type of its value was guessed.
+    guessed: Option<String>,
+}
+
+impl Applied {
+    /// Returns whether the variable overrides the value at the given dotted path or one of its parents.
+    fn overrides(&self, path: &str) -> bool {
+        path.strip_prefix(&self.path).is_some_and(|remainder| {
+            self.path.is_empty() || remainder.is_empty() || remainder.starts_with(['.', '['])
+        })
+    }
+}
+
+/// The dotted path and JSON Pointer of a value.
+#[derive(Default)]
+struct Location {
+    path: String,
+    pointer: String,
+}
+
+impl Location {
+    /// Prepends a map key or an array index to the location.
+    fn prepend(self, segment: &str, index: bool) -> Self {
+        let path = if index {
+            format!("[{segment}]{}", self.path)
+        } else if self.path.is_empty() || self.path.starts_with('[') {
+            format!("{segment}{}", self.path)
+        } else {
+            format!("{segment}.{}", self.path)
+        };
+        let pointer = format!(
+            "/{}{}",
+            segment.replace('~', "~0").replace('/', "~1"),
+            self.pointer
+        );
+        Self { path, pointer }
+    }
+}
+
+/// Returns the value at the given path, creating objects along the way as needed,
+/// alongside the location of the value.
+fn locate<'value>(value: &'value mut Value, segments: &[&str]) -> (&'value mut Value, Location) {
+    let Some((segment, remainder)) = segments.split_first() else {
+        return (value, Location::default());
+    };
+
+    let index = match value {
+        Value::Array(array) => segment
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < array.len()),
+        _ => None,
+    };
+
+    if let Some(index) = index {
+        let (target, location) = locate(&mut value[index], remainder);
+        return (target, location.prepend(&index.to_string(), true));
+    }
+
+    match value {
+        Value::Object(object) => {
+            let key = object
+                .keys()
+                .find(|key| key.eq_ignore_ascii_case(segment))
+                .cloned()
+                .unwrap_or_else(|| segment.to_ascii_lowercase());
+            let (target, location) =
+                locate(object.entry(key.clone()).or_insert(Value::Null), remainder);
+            (target, location.prepend(&key, false))
+        }
+        value => {
+            *value = Value::Object(Map::new());
+            locate(value, segments)
+        }
+    }
+}
+
+/// Parses the raw value of a variable according to the type of the value it overrides.
+fn parse(raw: &str, existing: &Value) -> Result<Value, String> {
+    match existing {
+        Value::String(_) => Ok(Value::String(raw.to_string())),
+        Value::Bool(_) => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(format!("expected a boolean, got {raw:?}")),
+        },
+        Value::Number(_) => serde_json::from_str::<Number>(raw.trim())
+            .map(Value::Number)
+            .map_err(|_| format!("expected a number, got {raw:?}")),
+        Value::Array(_) => match serde_json::from_str(raw) {
+            Ok(Value::Array(array)) => Ok(Value::Array(array)),
+            _ => Err(format!("expected a JSON-encoded array, got {raw:?}")),
+        },
+        Value::Object(_) => match serde_json::from_str(raw) {
+            Ok(Value::Object(object)) => Ok(Value::Object(object)),
+            _ => Err(format!("expected a JSON-encoded object, got {raw:?}")),
+        },
+        Value::Null => Ok(serde_json::from_str(raw)
+            .ok()
+            .filter(|value: &Value| !value.is_string())
+            .unwrap_or_else(|| Value::String(raw.to_string()))),
+    }
+}
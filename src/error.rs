@@ -1,31 +1,190 @@
 use std::ffi::OsString;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 /// Error type for de-/serialization operations from/to files.
+///
+/// New variants may be added in minor releases.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// An I/O error occurred while reading from or writing to the file.
     Io(std::io::Error),
     /// Serialization or deserialization failed.
-    Serde(Box<dyn std::error::Error>),
+    Serde(Box<dyn std::error::Error + Send + Sync>),
     /// The provided file extension does not indicate a supported file format.
     UnsupportedFileExtension(OsString),
     /// No file extension was specified.
     NoFileExtensionsSpecified,
+    /// The operation does not support the file format.
+    UnsupportedFormat(crate::Format),
+    /// Reading or writing an archive failed.
+    Archive(Box<dyn std::error::Error + Send + Sync>),
+    /// The requested entry does not exist within the archive.
+    ArchiveEntryNotFound(PathBuf),
+    /// The provided glob pattern is invalid.
+    InvalidPattern(Box<dyn std::error::Error + Send + Sync>),
+    /// Watching a file for changes failed.
+    Watch(Box<dyn std::error::Error + Send + Sync>),
+    /// The value of an environment variable could not be applied.
+    InvalidEnvVar {
+        /// The name of the environment variable.
+        name: String,
+        /// The reason why the value could not be applied.
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// An included file could not be loaded.
+    Include {
+        /// The files leading to the failing file, starting with the including file.
+        chain: Vec<PathBuf>,
+        /// The reason why the file could not be loaded.
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The requested previous version of a file does not exist.
+    BackupNotFound {
+        /// The file whose previous version was requested.
+        path: PathBuf,
+        /// The number of the previous version, counting from the most recent one.
+        number: usize,
+    },
+    /// No configuration file was found at any of the listed paths.
+    NoConfigFound(Vec<PathBuf>),
+    /// A file could not be converted into another format.
+    Transcode {
+        /// The format converted from.
+        from: crate::Format,
+        /// The format converted into.
+        to: crate::Format,
+        /// The reason why the conversion failed.
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A value of a file cannot be expressed in the format it is converted into.
+    Unrepresentable {
+        /// The format converted from.
+        from: crate::Format,
+        /// The format converted into.
+        to: crate::Format,
+        /// The path of the value, e.g. `servers[0].port`, which is empty for the top-level value.
+        path: String,
+        /// The kind of value that cannot be expressed.
+        value: crate::Unrepresentable,
+    },
+    /// The provided path into a value is invalid.
+    InvalidPath(String),
+    /// The provided path does not exist within the file.
+    PathNotFound(String),
+    /// An operation of a JSON Patch could not be applied.
+    Patch(Box<dyn std::error::Error + Send + Sync>),
+    /// The provided JSON Schema is invalid.
+    InvalidSchema(Box<dyn std::error::Error + Send + Sync>),
+    /// The value violates a JSON Schema.
+    ///
+    /// Holds the JSON pointer to each violating value within the document along with the violation.
+    SchemaViolations(Vec<(String, String)>),
+    /// The deserialized object violates its constraints.
+    Validation(crate::Violations),
+    /// The input contains keys that the deserialized type does not know.
+    UnknownKeys(Vec<crate::UnknownKey>),
+    /// The input contains keys that occur more than once within the same map.
+    DuplicateKeys(Vec<crate::DuplicateKey>),
+    /// A placeholder within a string value could not be expanded.
+    Interpolation {
+        /// The content of the placeholder.
+        placeholder: String,
+        /// The reason why the placeholder could not be expanded.
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::Io(e) => e.fmt(f),
-            Self::Serde(e) => e.fmt(f),
+            Self::Serde(e) | Self::Archive(e) | Self::Watch(e) => e.fmt(f),
             Self::UnsupportedFileExtension(extension) => {
                 write!(f, "Unsupported file extension: {}", extension.display())
             }
             Self::NoFileExtensionsSpecified => {
                 write!(f, "No file extension specified.")
             }
+            Self::UnsupportedFormat(format) => write!(f, "Unsupported format: {format}"),
+            Self::ArchiveEntryNotFound(entry) => {
+                write!(f, "Archive entry not found: {}", entry.display())
+            }
+            Self::InvalidPattern(e) => write!(f, "Invalid pattern: {e}"),
+            Self::InvalidPath(path) => write!(f, "Invalid path: {path}"),
+            Self::PathNotFound(path) => write!(f, "Path not found: {path}"),
+            Self::Validation(violations) => {
+                write!(f, "Validation failed:")?;
+                violations
+                    .iter()
+                    .try_for_each(|(path, message)| match path {
+                        "" => write!(f, "\n  {message}"),
+                        path => write!(f, "\n  {path}: {message}"),
+                    })
+            }
+            Self::UnknownKeys(keys) => {
+                write!(f, "Unknown keys:")?;
+                keys.iter().try_for_each(|key| write!(f, "\n  {key}"))
+            }
+            Self::DuplicateKeys(keys) => {
+                write!(f, "Duplicate keys:")?;
+                keys.iter().try_for_each(|key| write!(f, "\n  {key}"))
+            }
+            Self::Patch(e) => write!(f, "Cannot apply patch: {e}"),
+            Self::InvalidSchema(e) => write!(f, "Invalid schema: {e}"),
+            Self::SchemaViolations(violations) => {
+                write!(f, "Schema validation failed:")?;
+                violations.iter().try_for_each(|(path, message)| {
+                    let path = if path.is_empty() { "/" } else { path };
+                    write!(f, "\n  {path}: {message}")
+                })
+            }
+            Self::BackupNotFound { path, number } => {
+                write!(f, "Backup {number} of {} not found", path.display())
+            }
+            Self::NoConfigFound(tried) => {
+                write!(f, "No configuration file found. Tried:")?;
+                tried
+                    .iter()
+                    .try_for_each(|path| write!(f, "\n  {}", path.display()))
+            }
+            Self::InvalidEnvVar { name, source } => {
+                write!(f, "Invalid value of environment variable {name}: {source}")
+            }
+            Self::Include { chain, source } => {
+                let chain = chain
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                write!(f, "Cannot include {chain}: {source}")
+            }
+            Self::Transcode { from, to, source } => {
+                write!(f, "Cannot transcode {from} to {to}: {source}")
+            }
+            Self::Unrepresentable {
+                from,
+                to,
+                path,
+                value,
+            } => {
+                write!(
+                    f,
+                    "Cannot transcode {from} to {to}: {to} cannot represent the {value}"
+                )?;
+
+                if path.is_empty() {
+                    Ok(())
+                } else {
+                    write!(f, " at {path}")
+                }
+            }
+            Self::Interpolation {
+                placeholder,
+                source,
+            } => write!(f, "Cannot interpolate ${{{placeholder}}}: {source}"),
         }
     }
 }
@@ -34,8 +193,29 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Io(err) => Some(err),
-            Self::Serde(err) => Some(err.as_ref()),
-            Self::UnsupportedFileExtension(_) | Self::NoFileExtensionsSpecified => None,
+            Self::Serde(err)
+            | Self::Archive(err)
+            | Self::InvalidPattern(err)
+            | Self::Patch(err)
+            | Self::InvalidSchema(err)
+            | Self::Watch(err)
+            | Self::InvalidEnvVar { source: err, .. }
+            | Self::Include { source: err, .. }
+            | Self::Transcode { source: err, .. }
+            | Self::Interpolation { source: err, .. } => Some(err.as_ref()),
+            Self::UnsupportedFileExtension(_)
+            | Self::NoFileExtensionsSpecified
+            | Self::UnsupportedFormat(_)
+            | Self::ArchiveEntryNotFound(_)
+            | Self::BackupNotFound { .. }
+            | Self::NoConfigFound(_)
+            | Self::Unrepresentable { .. }
+            | Self::InvalidPath(_)
+            | Self::PathNotFound(_)
+            | Self::SchemaViolations(_)
+            | Self::Validation(_)
+            | Self::UnknownKeys(_)
+            | Self::DuplicateKeys(_) => None,
         }
     }
 }
@@ -46,6 +226,26 @@ impl From<std::io::Error> for Error {
     }
 }
 
+#[cfg(feature = "zip")]
+impl From<zip::result::ZipError> for Error {
+    fn from(err: zip::result::ZipError) -> Self {
+        match err {
+            zip::result::ZipError::Io(err) => Self::Io(err),
+            err => Self::Archive(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(feature = "watch")]
+impl From<notify::Error> for Error {
+    fn from(err: notify::Error) -> Self {
+        match err.kind {
+            notify::ErrorKind::Io(err) => Self::Io(err),
+            _ => Self::Watch(Box::new(err)),
+        }
+    }
+}
+
 #[cfg(feature = "json")]
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
@@ -53,6 +253,13 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "patch")]
+impl From<json_patch::PatchError> for Error {
+    fn from(err: json_patch::PatchError) -> Self {
+        Self::Patch(Box::new(err))
+    }
+}
+
 #[cfg(feature = "toml")]
 impl From<toml::de::Error> for Error {
     fn from(err: toml::de::Error) -> Self {
@@ -67,6 +274,13 @@ impl From<toml::ser::Error> for Error {
     }
 }
 
+#[cfg(feature = "toml-edit")]
+impl From<toml_edit::TomlError> for Error {
+    fn from(err: toml_edit::TomlError) -> Self {
+        Self::Serde(Box::new(err))
+    }
+}
+
 #[cfg(feature = "xml")]
 impl From<quick_xml::DeError> for Error {
     fn from(err: quick_xml::DeError) -> Self {
@@ -1,3 +1,18 @@
+use std::ffi::OsStr;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(unused_imports)
+)]
+use serde::de::IgnoredAny;
+
+use crate::compression::{decode, sniff};
+use crate::{Compression, Error};
+
 #[cfg(feature = "json")]
 pub mod json;
 #[cfg(feature = "toml")]
@@ -6,3 +21,341 @@ pub mod toml;
 pub mod xml;
 #[cfg(feature = "yaml")]
 pub mod yaml;
+
+/// The name and field of the struct by which TOML represents datetimes within serde's data model.
+#[cfg(all(feature = "value", feature = "toml"))]
+pub const TOML_DATETIME_NAME: &str = "$__toml_private_Datetime";
+#[cfg(any(feature = "value", all(feature = "schema", feature = "toml")))]
+pub const TOML_DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+#[cfg(feature = "xml")]
+const XML_INDENT_CHAR: char = ' ';
+#[cfg(feature = "xml")]
+const XML_INDENT_LEN: usize = 4;
+
+/// Supported file formats.
+#[allow(clippy::empty_enums)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// JavaScript Object Notation.
+    #[cfg(feature = "json")]
+    Json,
+    /// Tom's Obvious, Minimal Language.
+    #[cfg(feature = "toml")]
+    Toml,
+    /// Extensible Markup Language.
+    #[cfg(feature = "xml")]
+    Xml,
+    /// YAML Ain't Markup Language.
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// Returns the format indicated by the given file extension, if it is supported.
+    ///
+    /// The comparison is case-insensitive.
+    #[must_use]
+    pub fn from_extension(extension: &OsStr) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_encoded_bytes() {
+            #[cfg(feature = "json")]
+            b"json" => Some(Self::Json),
+            #[cfg(feature = "toml")]
+            b"toml" => Some(Self::Toml),
+            #[cfg(feature = "xml")]
+            b"xml" => Some(Self::Xml),
+            #[cfg(feature = "yaml")]
+            b"yml" | b"yaml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Returns the format of the given text, if it is valid in any supported format.
+    ///
+    /// The formats are tried from the strictest to the most lenient, i.e. JSON, TOML, XML and YAML.
+    /// XML is only considered for texts starting with a tag.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::Format;
+    ///
+    /// #[cfg(all(feature = "json", feature = "toml", feature = "yaml"))]
+    /// {
+    ///     assert_eq!(Format::from_content(r#"{"id": 1337}"#), Some(Format::Json));
+    ///     assert_eq!(Format::from_content("id = 1337\n"), Some(Format::Toml));
+    ///     assert_eq!(Format::from_content("id: 1337\n"), Some(Format::Yaml));
+    /// }
+    /// ```
+    #[cfg_attr(
+        not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+        allow(unused_variables, clippy::missing_const_for_fn)
+    )]
+    #[must_use]
+    pub fn from_content(text: &str) -> Option<Self> {
+        #[cfg(feature = "json")]
+        if Self::Json.deserialize::<IgnoredAny>(text).is_ok() {
+            return Some(Self::Json);
+        }
+
+        #[cfg(feature = "toml")]
+        if Self::Toml.deserialize::<IgnoredAny>(text).is_ok() {
+            return Some(Self::Toml);
+        }
+
+        #[cfg(feature = "xml")]
+        if text.trim_start().starts_with('<') && Self::Xml.deserialize::<IgnoredAny>(text).is_ok() {
+            return Some(Self::Xml);
+        }
+
+        #[cfg(feature = "yaml")]
+        if Self::Yaml.deserialize::<IgnoredAny>(text).is_ok() {
+            return Some(Self::Yaml);
+        }
+
+        None
+    }
+
+    /// Deserializes an object from a string in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the deserialization fails.
+    #[cfg_attr(
+        not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+        allow(unused_variables, clippy::missing_const_for_fn)
+    )]
+    pub fn deserialize<T>(self, text: &str) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => <T as crate::FromJson>::from_json_string(text),
+            #[cfg(feature = "toml")]
+            Self::Toml => <T as crate::FromToml>::from_toml_string(text),
+            #[cfg(feature = "xml")]
+            Self::Xml => <T as crate::FromXml>::from_xml_string(text),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => <T as crate::FromYaml>::from_yaml_string(text),
+        }
+    }
+
+    /// Deserializes an object from a string in this format
+    /// and returns the keys of the input that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the deserialization fails.
+    #[cfg(feature = "strict")]
+    #[cfg_attr(
+        not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+        allow(unused_variables, clippy::missing_const_for_fn)
+    )]
+    pub fn deserialize_with_unknown_keys<T>(
+        self,
+        text: &str,
+    ) -> crate::Result<(T, Vec<crate::UnknownKey>)>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(text);
+                let result = crate::strict::deserialize(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(result)
+            }
+            #[cfg(feature = "toml")]
+            Self::Toml => Ok(crate::strict::deserialize(::toml::Deserializer::parse(
+                text,
+            )?)?),
+            #[cfg(feature = "xml")]
+            Self::Xml => Ok(crate::strict::deserialize(
+                &mut quick_xml::de::Deserializer::from_str(text),
+            )?),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => Ok(crate::strict::deserialize(
+                serde_yaml::Deserializer::from_str(text),
+            )?),
+        }
+    }
+
+    /// Serializes an object into a string in this format.
+    ///
+    /// If `pretty` is set, the output is prettified where the format supports it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the serialization fails.
+    pub fn serialize<T>(self, value: &T, pretty: bool) -> crate::Result<String>
+    where
+        T: Serialize,
+    {
+        serialize(self, value, pretty, None)
+    }
+
+    /// Serializes an object into a string in this format using the given write options.
+    ///
+    /// This honors whether the output shall be prettified and the name of the XML root element, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use serde_rw::{Format, WriteOptions};
+    ///
+    /// #[cfg(feature = "xml")]
+    /// {
+    ///     let map = BTreeMap::from([("id", 1337)]);
+    ///     let options = WriteOptions::default().xml_root("person");
+    ///     assert_eq!(
+    ///         Format::Xml.serialize_with(&map, &options).unwrap(),
+    ///         "<person><id>1337</id></person>"
+    ///     );
+    /// }
+    /// ```
+    pub fn serialize_with<T>(
+        self,
+        value: &T,
+        options: &crate::WriteOptions,
+    ) -> crate::Result<String>
+    where
+        T: Serialize,
+    {
+        serialize(self, value, options.is_pretty(), options.get_xml_root())
+    }
+}
+
+/// Serializes an object into a string in the given format, enclosed by the given root element in XML.
+#[cfg_attr(
+    not(feature = "xml"),
+    allow(unused_variables, clippy::missing_const_for_fn)
+)]
+pub fn serialize<T>(
+    format: Format,
+    value: &T,
+    pretty: bool,
+    root: Option<&str>,
+) -> crate::Result<String>
+where
+    T: Serialize,
+{
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => {
+            if pretty {
+                <T as crate::ToJson>::to_json_pretty(value)
+            } else {
+                <T as crate::ToJson>::to_json(value)
+            }
+        }
+        #[cfg(feature = "toml")]
+        Format::Toml => <T as crate::ToToml>::to_toml(value),
+        #[cfg(feature = "xml")]
+        Format::Xml => match (root, pretty) {
+            (Some(root), true) => <T as crate::ToXml>::to_xml_pretty_with_root(
+                value,
+                root,
+                XML_INDENT_CHAR,
+                XML_INDENT_LEN,
+            ),
+            (Some(root), false) => <T as crate::ToXml>::to_xml_with_root(value, root),
+            (None, true) => {
+                <T as crate::ToXml>::to_xml_pretty(value, XML_INDENT_CHAR, XML_INDENT_LEN)
+            }
+            (None, false) => <T as crate::ToXml>::to_xml(value),
+        },
+        #[cfg(feature = "yaml")]
+        Format::Yaml => <T as crate::ToYaml>::to_yaml(value),
+    }
+}
+
+/// A kind of value which a format cannot represent.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Unrepresentable {
+    /// A `null` value, which TOML cannot represent.
+    Null,
+    /// A map key other than a string in TOML or other than a string, number or boolean in JSON.
+    Key,
+    /// A top-level value other than a table in TOML or, lacking the name of a root element, any value in XML.
+    TopLevel,
+}
+
+impl Display for Unrepresentable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null value"),
+            Self::Key => f.write_str("map key"),
+            Self::TopLevel => f.write_str("top-level value"),
+        }
+    }
+}
+
+impl Display for Format {
+    #[cfg_attr(
+        not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+        allow(unused_variables, clippy::uninhabited_references)
+    )]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "json")]
+            Self::Json => f.write_str("JSON"),
+            #[cfg(feature = "toml")]
+            Self::Toml => f.write_str("TOML"),
+            #[cfg(feature = "xml")]
+            Self::Xml => f.write_str("XML"),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => f.write_str("YAML"),
+        }
+    }
+}
+
+/// Determines the file format and compression from the file extension(s) of the given path.
+///
+/// If the last extension denotes a supported compression, the format is taken from the
+/// extension before it, e.g. `data.json.gz` is gzip-compressed JSON.
+pub fn detect(path: &Path) -> crate::Result<(Format, Option<Compression>)> {
+    let mut extension = path
+        .extension()
+        .map(OsStr::to_ascii_lowercase)
+        .ok_or(Error::NoFileExtensionsSpecified)?;
+    let compression = Compression::from_extension(&extension);
+
+    if compression.is_some() {
+        extension = path
+            .file_stem()
+            .map(Path::new)
+            .and_then(Path::extension)
+            .map(OsStr::to_ascii_lowercase)
+            .ok_or(Error::NoFileExtensionsSpecified)?;
+    }
+
+    Format::from_extension(&extension)
+        .map(|format| (format, compression))
+        .ok_or(Error::UnsupportedFileExtension(extension))
+}
+
+/// Determines the file format of raw file content and decodes it into text.
+///
+/// The format and compression are taken from the file extension(s), see [`detect`].
+/// Files with an unknown or no format extension, e.g. `config.conf`, `config` or `config.gz`,
+/// are detected by their content: the compression by a compression extension or the magic bytes
+/// and the format by [`Format::from_content`]. If that fails, the error of [`detect`] is returned.
+pub fn detect_content(path: &Path, bytes: Vec<u8>) -> crate::Result<(Format, String)> {
+    match detect(path) {
+        Ok((format, compression)) => Ok((format, decode(bytes, compression)?)),
+        Err(error @ (Error::NoFileExtensionsSpecified | Error::UnsupportedFileExtension(_))) => {
+            let compression = sniff(path, &bytes);
+            decode(bytes, compression)
+                .ok()
+                .and_then(|text| Format::from_content(&text).map(|format| (format, text)))
+                .ok_or(error)
+        }
+        Err(error) => Err(error),
+    }
+}
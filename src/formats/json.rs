@@ -72,6 +72,109 @@ pub trait FromJson: for<'de> Deserialize<'de> {
     fn from_json_string(text: &str) -> crate::Result<Self> {
         Ok(serde_json::from_str(text)?)
     }
+
+    /// Deserializes an object from a JSON file and returns the keys of the file that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_json_file_with_unknown_keys(
+        filename: impl AsRef<Path>,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        <Self as FromJson>::from_json_string_with_unknown_keys(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from a JSON file and rejects keys of the file that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the file contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_json_file_strict(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        <Self as FromJson>::from_json_string_strict(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from a JSON string and returns the keys of the string that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_json_string_with_unknown_keys(
+        text: &str,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        crate::Format::Json.deserialize_with_unknown_keys(text)
+    }
+
+    /// Deserializes an object from a JSON string and rejects keys of the string that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the string contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromJson;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Person {
+    ///     id: u32,
+    /// }
+    ///
+    /// const JSON: &str = r#"{"id": 1337, "name": "John Doe"}"#;
+    ///
+    /// #[cfg(all(feature = "json", feature = "strict"))]
+    /// {
+    ///     let (person, unknown_keys) = Person::from_json_string_with_unknown_keys(JSON).unwrap();
+    ///     assert_eq!(person.id, 1337);
+    ///     assert_eq!(unknown_keys[0].path(), "name");
+    ///     assert!(matches!(
+    ///         Person::from_json_string_strict(JSON),
+    ///         Err(serde_rw::Error::UnknownKeys(_))
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(feature = "strict")]
+    fn from_json_string_strict(text: &str) -> crate::Result<Self> {
+        let (value, unknown_keys) = <Self as FromJson>::from_json_string_with_unknown_keys(text)?;
+        crate::strict::deny(unknown_keys)?;
+        Ok(value)
+    }
+
+    /// Asynchronously deserializes an object from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_json_file_async(
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<Self>> + Send {
+        async move { <Self as FromJson>::from_json_string(&tokio::fs::read_to_string(filename).await?) }
+    }
+
+    /// Asynchronously deserializes an object from a JSON [reader](tokio::io::AsyncRead).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_json_reader_async<R>(reader: R) -> impl Future<Output = crate::Result<Self>> + Send
+    where
+        R: tokio::io::AsyncRead + Send + Unpin,
+    {
+        async move {
+            <Self as FromJson>::from_json_string(&crate::asynchronous::read_text(reader).await?)
+        }
+    }
 }
 
 /// Allow serialization to JSON.
@@ -136,4 +239,136 @@ pub trait ToJson: Serialize {
     fn write_to_json_file_pretty(&self, filename: impl AsRef<Path>) -> crate::Result<()> {
         Ok(write(filename, <Self as ToJson>::to_json_pretty(self)?)?)
     }
+
+    /// Updates an object stored in a JSON file, changing only the values whose serialization differs.
+    ///
+    /// The object is deserialized from the file and passed to the function.
+    /// Afterwards, only the changed values are rewritten within the text,
+    /// so that comments, formatting and keys unknown to the object are preserved.
+    /// The file is written atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read, parsed or written
+    /// or if the object cannot be serialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::ToJson;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "edit")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_update_person.json");
+    ///     std::fs::write(&filename, "{\n  // unique\n  \"id\": 1337,\n  \"name\": \"John Doe\"\n}\n").unwrap();
+    ///
+    ///     Person::update_json_file(&filename, |person| person.id = 42).unwrap();
+    ///     assert_eq!(
+    ///         std::fs::read_to_string(&filename).unwrap(),
+    ///         "{\n  // unique\n  \"id\": 42,\n  \"name\": \"John Doe\"\n}\n"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "edit")]
+    fn update_json_file<F, R>(filename: impl AsRef<Path>, function: F) -> crate::Result<R>
+    where
+        Self: serde::de::DeserializeOwned,
+        F: FnOnce(&mut Self) -> R,
+    {
+        Self::update_json_file_with(
+            filename,
+            &crate::WriteOptions::default().atomic(true),
+            function,
+        )
+    }
+
+    /// Like [`ToJson::update_json_file`], but writes the file using the given options.
+    ///
+    /// See [`Document::write_to_file_with`](crate::Document::write_to_file_with) for how the options apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read, parsed or written
+    /// or if the object cannot be serialized.
+    #[cfg(feature = "edit")]
+    fn update_json_file_with<F, R>(
+        filename: impl AsRef<Path>,
+        options: &crate::WriteOptions,
+        function: F,
+    ) -> crate::Result<R>
+    where
+        Self: serde::de::DeserializeOwned,
+        F: FnOnce(&mut Self) -> R,
+    {
+        crate::edit::update_file(filename.as_ref(), crate::Format::Json, options, function)
+    }
+
+    /// Asynchronously write object as JSON to a [writer](tokio::io::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_json_async<W>(&self, writer: W) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        let text = <Self as ToJson>::to_json(self);
+        async move { crate::asynchronous::write_text(writer, &text?).await }
+    }
+
+    /// Asynchronously write object as pretty JSON to a [writer](tokio::io::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_json_pretty_async<W>(
+        &self,
+        writer: W,
+    ) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        let text = <Self as ToJson>::to_json_pretty(self);
+        async move { crate::asynchronous::write_text(writer, &text?).await }
+    }
+
+    /// Asynchronously writes object as serialized JSON string to a file.
+    ///
+    /// The file is written atomically, see [`WriteOptions::atomic`](crate::WriteOptions::atomic).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_to_json_file_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        let text = <Self as ToJson>::to_json(self);
+        async move { crate::asynchronous::write_text_file(filename.as_ref(), &text?).await }
+    }
+
+    /// Asynchronously writes object as prettified serialized JSON string to a file.
+    ///
+    /// The file is written atomically, see [`WriteOptions::atomic`](crate::WriteOptions::atomic).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_to_json_file_pretty_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        let text = <Self as ToJson>::to_json_pretty(self);
+        async move { crate::asynchronous::write_text_file(filename.as_ref(), &text?).await }
+    }
 }
@@ -1,9 +1,35 @@
+use std::cell::Cell;
 use std::fs::{read_to_string, write};
 use std::path::Path;
 
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
+#[cfg(feature = "toml-edit")]
+mod edit;
+
+thread_local! {
+    /// Whether an object is being serialized into TOML on this thread.
+    static SERIALIZING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Serializes into TOML within the given function.
+///
+/// This tells [`Value`](crate::Value)s to pass on datetimes in TOML's representation,
+/// since serializers do not reveal their format.
+pub fn serializing<R>(function: impl FnOnce() -> R) -> R {
+    let previous = SERIALIZING.replace(true);
+    let result = function();
+    SERIALIZING.set(previous);
+    result
+}
+
+/// Returns whether an object is being serialized into TOML on this thread, see [`serializing`].
+#[cfg_attr(not(feature = "value"), allow(dead_code))]
+pub fn is_serializing() -> bool {
+    SERIALIZING.get()
+}
+
 /// Allow deserialization from TOML.
 #[allow(clippy::module_name_repetitions)]
 pub trait FromToml: DeserializeOwned {
@@ -73,6 +99,109 @@ pub trait FromToml: DeserializeOwned {
     fn from_toml_string(text: &str) -> crate::Result<Self> {
         Ok(toml::from_str(text)?)
     }
+
+    /// Deserializes an object from a TOML file and returns the keys of the file that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_toml_file_with_unknown_keys(
+        filename: impl AsRef<Path>,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        <Self as FromToml>::from_toml_string_with_unknown_keys(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from a TOML file and rejects keys of the file that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the file contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_toml_file_strict(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        <Self as FromToml>::from_toml_string_strict(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from a TOML string and returns the keys of the string that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_toml_string_with_unknown_keys(
+        text: &str,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        crate::Format::Toml.deserialize_with_unknown_keys(text)
+    }
+
+    /// Deserializes an object from a TOML string and rejects keys of the string that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the string contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromToml;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Person {
+    ///     id: u32,
+    /// }
+    ///
+    /// const TOML: &str = "id = 1337\nname = \"John Doe\"";
+    ///
+    /// #[cfg(all(feature = "toml", feature = "strict"))]
+    /// {
+    ///     let (person, unknown_keys) = Person::from_toml_string_with_unknown_keys(TOML).unwrap();
+    ///     assert_eq!(person.id, 1337);
+    ///     assert_eq!(unknown_keys[0].path(), "name");
+    ///     assert!(matches!(
+    ///         Person::from_toml_string_strict(TOML),
+    ///         Err(serde_rw::Error::UnknownKeys(_))
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(feature = "strict")]
+    fn from_toml_string_strict(text: &str) -> crate::Result<Self> {
+        let (value, unknown_keys) = <Self as FromToml>::from_toml_string_with_unknown_keys(text)?;
+        crate::strict::deny(unknown_keys)?;
+        Ok(value)
+    }
+
+    /// Asynchronously deserializes an object from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_toml_file_async(
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<Self>> + Send {
+        async move { <Self as FromToml>::from_toml_string(&tokio::fs::read_to_string(filename).await?) }
+    }
+
+    /// Asynchronously deserializes an object from a TOML [reader](tokio::io::AsyncRead).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_toml_reader_async<R>(reader: R) -> impl Future<Output = crate::Result<Self>> + Send
+    where
+        R: tokio::io::AsyncRead + Send + Unpin,
+    {
+        async move {
+            <Self as FromToml>::from_toml_string(&crate::asynchronous::read_text(reader).await?)
+        }
+    }
 }
 
 /// Allow serialization to TOML.
@@ -84,7 +213,7 @@ pub trait ToToml: Serialize {
     ///
     /// Returns an [`Error`](crate::Error) if the serialization fails.
     fn to_toml(&self) -> crate::Result<String> {
-        Ok(toml::to_string(self)?)
+        Ok(serializing(|| toml::to_string(self))?)
     }
 
     /// Writes object as serialized TOML string to a file.
@@ -95,4 +224,147 @@ pub trait ToToml: Serialize {
     fn write_to_toml_file(&self, filename: impl AsRef<Path>) -> crate::Result<()> {
         Ok(write(filename, <Self as ToToml>::to_toml(self)?)?)
     }
+
+    /// Updates an object stored in a TOML file, changing only the keys whose values differ.
+    ///
+    /// The object is deserialized from the file and passed to the function.
+    /// Afterwards, only the differences between its serialized values before and after the call
+    /// are applied to the document, so that comments, whitespace, the order of keys
+    /// and keys unknown to the object are preserved everywhere else.
+    /// The file is written atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read, parsed or written
+    /// or if the object cannot be serialized as a TOML table.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::ToToml;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "toml-edit")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_update_person.toml");
+    ///     std::fs::write(&filename, "# The person's ID.\nid = 1337 # unique\nname = \"John Doe\"\n").unwrap();
+    ///
+    ///     Person::update_toml_file(&filename, |person| person.id = 42).unwrap();
+    ///     assert_eq!(
+    ///         std::fs::read_to_string(&filename).unwrap(),
+    ///         "# The person's ID.\nid = 42 # unique\nname = \"John Doe\"\n"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "toml-edit")]
+    fn update_toml_file<F, R>(filename: impl AsRef<Path>, function: F) -> crate::Result<R>
+    where
+        Self: DeserializeOwned,
+        F: FnOnce(&mut Self) -> R,
+    {
+        Self::update_toml_file_with(
+            filename,
+            &crate::WriteOptions::default().atomic(true),
+            function,
+        )
+    }
+
+    /// Like [`ToToml::update_toml_file`], but writes the file using the given options.
+    ///
+    /// Options concerning serialization, such as prettifying, do not apply, since the document is edited in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read, parsed or written
+    /// or if the object cannot be serialized as a TOML table.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Backup, ToToml, WriteOptions};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "toml-edit")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_update_person_with.toml");
+    ///     std::fs::write(&filename, "id = 1337 # unique\nname = \"John Doe\"\n").unwrap();
+    ///
+    ///     let options = WriteOptions::default().atomic(true).backup(Backup::Single);
+    ///     Person::update_toml_file_with(&filename, &options, |person| person.id = 42).unwrap();
+    ///     assert_eq!(
+    ///         std::fs::read_to_string(filename.with_extension("toml.bak")).unwrap(),
+    ///         "id = 1337 # unique\nname = \"John Doe\"\n"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "toml-edit")]
+    fn update_toml_file_with<F, R>(
+        filename: impl AsRef<Path>,
+        options: &crate::WriteOptions,
+        function: F,
+    ) -> crate::Result<R>
+    where
+        Self: DeserializeOwned,
+        F: FnOnce(&mut Self) -> R,
+    {
+        let filename = filename.as_ref();
+        let text = read_to_string(filename)?;
+        let mut document: toml_edit::DocumentMut = text.parse()?;
+        let mut object: Self = toml::from_str(&text)?;
+        let old = serializing(|| toml::Table::try_from(&object))?;
+        let result = function(&mut object);
+        edit::update_table(
+            document.as_table_mut(),
+            &old,
+            &serializing(|| toml::Table::try_from(&object))?,
+        );
+        let updated = document.to_string();
+
+        // Only the changed keys are edited, so the text is unchanged if and only if the data is.
+        if options.get_if_changed().is_none() || updated != text {
+            crate::to_file::write_bytes(filename, updated.as_bytes(), options)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Asynchronously write object as TOML to a [writer](tokio::io::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_toml_async<W>(&self, writer: W) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        let text = <Self as ToToml>::to_toml(self);
+        async move { crate::asynchronous::write_text(writer, &text?).await }
+    }
+
+    /// Asynchronously writes object as serialized TOML string to a file.
+    ///
+    /// The file is written atomically, see [`WriteOptions::atomic`](crate::WriteOptions::atomic).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_to_toml_file_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        let text = <Self as ToToml>::to_toml(self);
+        async move { crate::asynchronous::write_text_file(filename.as_ref(), &text?).await }
+    }
 }
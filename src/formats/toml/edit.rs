@@ -0,0 +1,129 @@
+use toml::{Table, Value};
+use toml_edit::{ArrayOfTables, Item, TableLike};
+
+/// Applies the differences between two serialized tables onto an editable table.
+///
+/// Keys whose values did not change are left untouched, as are keys unknown to both tables.
+/// Thus comments, whitespace and the order of keys are preserved wherever possible.
+pub fn update_table(table: &mut dyn TableLike, old: &Table, new: &Table) {
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        table.remove(key);
+    }
+
+    for (key, new) in new {
+        match (table.get_mut(key), old.get(key)) {
+            (Some(_), Some(old)) if old == new => {}
+            (Some(item), Some(old)) => update_item(item, old, new),
+            _ => {
+                table.insert(key, item(new));
+            }
+        }
+    }
+}
+
+/// Applies the differences between two serialized values onto an editable item.
+fn update_item(item: &mut Item, old: &Value, new: &Value) {
+    match (item, old, new) {
+        (Item::Table(table), Value::Table(old), Value::Table(new)) => {
+            update_table(table, old, new);
+        }
+        (Item::ArrayOfTables(array), Value::Array(old), Value::Array(new))
+            if new.iter().all(Value::is_table) =>
+        {
+            update_array_of_tables(array, old, new);
+        }
+        (Item::Value(value), old, new) => update_value(value, old, new),
+        (item, _, new) => *item = self::item(new),
+    }
+}
+
+/// Applies the differences between two serialized values onto an editable value,
+/// keeping its surrounding whitespace and comments.
+fn update_value(value: &mut toml_edit::Value, old: &Value, new: &Value) {
+    match (value, old, new) {
+        (_, old, new) if old == new => {}
+        (toml_edit::Value::InlineTable(table), Value::Table(old), Value::Table(new)) => {
+            update_table(table, old, new);
+        }
+        (toml_edit::Value::Array(array), Value::Array(old), Value::Array(new)) => {
+            for (index, new) in new.iter().enumerate() {
+                match (array.get_mut(index), old.get(index)) {
+                    (Some(value), Some(old)) => update_value(value, old, new),
+                    _ => array.push(self::value(new)),
+                }
+            }
+
+            while array.len() > new.len() {
+                array.remove(array.len() - 1);
+            }
+        }
+        (value, _, new) => {
+            let decor = value.decor().clone();
+            *value = self::value(new);
+            *value.decor_mut() = decor;
+        }
+    }
+}
+
+/// Applies the differences between two serialized arrays of tables onto an editable array of tables.
+fn update_array_of_tables(array: &mut ArrayOfTables, old: &[Value], new: &[Value]) {
+    for (index, new) in new.iter().enumerate() {
+        match (array.get_mut(index), old.get(index), new) {
+            (Some(table), Some(Value::Table(old)), Value::Table(new)) => {
+                update_table(table, old, new);
+            }
+            (_, _, Value::Table(new)) => array.push(table(new)),
+            _ => {}
+        }
+    }
+
+    while array.len() > new.len() {
+        array.remove(array.len() - 1);
+    }
+}
+
+/// Converts a serialized value into a new item,
+/// using standard tables and arrays of tables where TOML would.
+fn item(value: &Value) -> Item {
+    match value {
+        Value::Table(table) => Item::Table(self::table(table)),
+        Value::Array(array) if !array.is_empty() && array.iter().all(Value::is_table) => {
+            Item::ArrayOfTables(
+                array
+                    .iter()
+                    .filter_map(Value::as_table)
+                    .map(table)
+                    .collect(),
+            )
+        }
+        value => Item::Value(self::value(value)),
+    }
+}
+
+fn table(table: &Table) -> toml_edit::Table {
+    let mut result = toml_edit::Table::new();
+
+    for (key, value) in table {
+        result.insert(key, item(value));
+    }
+
+    result
+}
+
+/// Converts a serialized value into a new inline value.
+fn value(value: &Value) -> toml_edit::Value {
+    match value {
+        Value::String(string) => string.clone().into(),
+        Value::Integer(integer) => (*integer).into(),
+        Value::Float(float) => (*float).into(),
+        Value::Boolean(boolean) => (*boolean).into(),
+        Value::Datetime(datetime) => (*datetime).into(),
+        Value::Array(array) => toml_edit::Value::Array(array.iter().map(self::value).collect()),
+        Value::Table(table) => toml_edit::Value::InlineTable(
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), self::value(value)))
+                .collect(),
+        ),
+    }
+}
@@ -74,6 +74,107 @@ pub trait FromXml: DeserializeOwned {
     fn from_xml_string(text: &str) -> crate::Result<Self> {
         Ok(quick_xml::de::from_str(text)?)
     }
+
+    /// Deserializes an object from an XML file and returns the keys of the file that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_xml_file_with_unknown_keys(
+        filename: impl AsRef<Path>,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        <Self as FromXml>::from_xml_string_with_unknown_keys(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from an XML file and rejects keys of the file that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the file contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_xml_file_strict(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        <Self as FromXml>::from_xml_string_strict(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from an XML string and returns the keys of the string that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_xml_string_with_unknown_keys(
+        text: &str,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        crate::Format::Xml.deserialize_with_unknown_keys(text)
+    }
+
+    /// Deserializes an object from an XML string and rejects keys of the string that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the string contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromXml;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Person {
+    ///     id: u32,
+    /// }
+    ///
+    /// const XML: &str = "<person><id>1337</id><name>John Doe</name></person>";
+    ///
+    /// #[cfg(all(feature = "xml", feature = "strict"))]
+    /// {
+    ///     let (person, unknown_keys) = Person::from_xml_string_with_unknown_keys(XML).unwrap();
+    ///     assert_eq!(person.id, 1337);
+    ///     assert_eq!(unknown_keys[0].path(), "name");
+    ///     assert!(matches!(
+    ///         Person::from_xml_string_strict(XML),
+    ///         Err(serde_rw::Error::UnknownKeys(_))
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(feature = "strict")]
+    fn from_xml_string_strict(text: &str) -> crate::Result<Self> {
+        let (value, unknown_keys) = <Self as FromXml>::from_xml_string_with_unknown_keys(text)?;
+        crate::strict::deny(unknown_keys)?;
+        Ok(value)
+    }
+
+    /// Asynchronously deserializes an object from an XML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_xml_file_async(
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<Self>> + Send {
+        async move { <Self as FromXml>::from_xml_string(&tokio::fs::read_to_string(filename).await?) }
+    }
+
+    /// Asynchronously deserializes an object from an XML [reader](tokio::io::AsyncRead).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_xml_reader_async<R>(reader: R) -> impl Future<Output = crate::Result<Self>> + Send
+    where
+        R: tokio::io::AsyncRead + Send + Unpin,
+    {
+        async move { <Self as FromXml>::from_xml_string(&crate::asynchronous::read_text(reader).await?) }
+    }
 }
 
 /// Allow serialization to XML.
@@ -113,6 +214,47 @@ pub trait ToXml: Serialize {
         Ok(buffer)
     }
 
+    /// Return object as serialized XML string within a root element of the given name.
+    ///
+    /// This allows serializing values without a name of their own, e.g. maps, which XML requires to be enclosed by a root element.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails, e.g. if the name is not a valid XML name.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use serde_rw::ToXml;
+    ///
+    /// let map = BTreeMap::from([("id", 1337)]);
+    /// assert_eq!(map.to_xml_with_root("person").unwrap(), "<person><id>1337</id></person>");
+    /// ```
+    fn to_xml_with_root(&self, root: &str) -> crate::Result<String> {
+        Ok(quick_xml::se::to_string_with_root(root, self)?)
+    }
+
+    /// Return object as a pretty serialized XML string within a root element of the given name.
+    ///
+    /// See [`ToXml::to_xml_with_root`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails, e.g. if the name is not a valid XML name.
+    fn to_xml_pretty_with_root(
+        &self,
+        root: &str,
+        indent_char: char,
+        indent_size: usize,
+    ) -> crate::Result<String> {
+        let mut buffer = String::new();
+        let mut serializer = Serializer::with_root(&mut buffer, Some(root))?;
+        serializer.indent(indent_char, indent_size);
+        self.serialize(serializer)?;
+        Ok(buffer)
+    }
+
     /// Writes object as serialized XML string to a file.
     ///
     /// # Errors
@@ -138,4 +280,52 @@ pub trait ToXml: Serialize {
             <Self as ToXml>::to_xml_pretty(self, indent_char, indent_size)?,
         )?)
     }
+
+    /// Asynchronously write object as XML to a [writer](tokio::io::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_xml_async<W>(&self, writer: W) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        let text = <Self as ToXml>::to_xml(self);
+        async move { crate::asynchronous::write_text(writer, &text?).await }
+    }
+
+    /// Asynchronously writes object as serialized XML string to a file.
+    ///
+    /// The file is written atomically, see [`WriteOptions::atomic`](crate::WriteOptions::atomic).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_to_xml_file_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        let text = <Self as ToXml>::to_xml(self);
+        async move { crate::asynchronous::write_text_file(filename.as_ref(), &text?).await }
+    }
+
+    /// Asynchronously writes object as a pretty serialized XML string to a file.
+    ///
+    /// The file is written atomically, see [`WriteOptions::atomic`](crate::WriteOptions::atomic).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_to_xml_file_pretty_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+        indent_char: char,
+        indent_size: usize,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        let text = <Self as ToXml>::to_xml_pretty(self, indent_char, indent_size);
+        async move { crate::asynchronous::write_text_file(filename.as_ref(), &text?).await }
+    }
 }
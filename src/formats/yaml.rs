@@ -72,6 +72,109 @@ pub trait FromYaml: for<'de> Deserialize<'de> {
     fn from_yaml_string(text: &str) -> crate::Result<Self> {
         Ok(serde_yaml::from_str(text)?)
     }
+
+    /// Deserializes an object from a YAML file and returns the keys of the file that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_yaml_file_with_unknown_keys(
+        filename: impl AsRef<Path>,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        <Self as FromYaml>::from_yaml_string_with_unknown_keys(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from a YAML file and rejects keys of the file that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the file contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_yaml_file_strict(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        <Self as FromYaml>::from_yaml_string_strict(&read_to_string(filename)?)
+    }
+
+    /// Deserializes an object from a YAML string and returns the keys of the string that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`](crate::FromFile::from_file_with_unknown_keys) for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "strict")]
+    fn from_yaml_string_with_unknown_keys(
+        text: &str,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        crate::Format::Yaml.deserialize_with_unknown_keys(text)
+    }
+
+    /// Deserializes an object from a YAML string and rejects keys of the string that the object did not use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the string contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromYaml;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Person {
+    ///     id: u32,
+    /// }
+    ///
+    /// const YAML: &str = "id: 1337\nname: John Doe";
+    ///
+    /// #[cfg(all(feature = "yaml", feature = "strict"))]
+    /// {
+    ///     let (person, unknown_keys) = Person::from_yaml_string_with_unknown_keys(YAML).unwrap();
+    ///     assert_eq!(person.id, 1337);
+    ///     assert_eq!(unknown_keys[0].path(), "name");
+    ///     assert!(matches!(
+    ///         Person::from_yaml_string_strict(YAML),
+    ///         Err(serde_rw::Error::UnknownKeys(_))
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(feature = "strict")]
+    fn from_yaml_string_strict(text: &str) -> crate::Result<Self> {
+        let (value, unknown_keys) = <Self as FromYaml>::from_yaml_string_with_unknown_keys(text)?;
+        crate::strict::deny(unknown_keys)?;
+        Ok(value)
+    }
+
+    /// Asynchronously deserializes an object from a YAML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_yaml_file_async(
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<Self>> + Send {
+        async move { <Self as FromYaml>::from_yaml_string(&tokio::fs::read_to_string(filename).await?) }
+    }
+
+    /// Asynchronously deserializes an object from a YAML [reader](tokio::io::AsyncRead).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    #[cfg(feature = "tokio")]
+    fn from_yaml_reader_async<R>(reader: R) -> impl Future<Output = crate::Result<Self>> + Send
+    where
+        R: tokio::io::AsyncRead + Send + Unpin,
+    {
+        async move {
+            <Self as FromYaml>::from_yaml_string(&crate::asynchronous::read_text(reader).await?)
+        }
+    }
 }
 
 /// Allow serialization to YAML.
@@ -94,4 +197,103 @@ pub trait ToYaml: Serialize {
     fn write_to_yaml_file(&self, filename: impl AsRef<Path>) -> crate::Result<()> {
         Ok(write(filename, <Self as ToYaml>::to_yaml(self)?)?)
     }
+
+    /// Updates an object stored in a YAML file, changing only the values whose serialization differs.
+    ///
+    /// The object is deserialized from the file and passed to the function.
+    /// Afterwards, only the changed values are rewritten within the text,
+    /// so that comments, formatting and keys unknown to the object are preserved.
+    /// The file is written atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read, parsed or written
+    /// or if the object cannot be serialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::ToYaml;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "yaml-edit")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_update_person.yml");
+    ///     std::fs::write(&filename, "# A person.\nid: 1337 # unique\nname: John Doe\n").unwrap();
+    ///
+    ///     Person::update_yaml_file(&filename, |person| person.id = 42).unwrap();
+    ///     assert_eq!(
+    ///         std::fs::read_to_string(&filename).unwrap(),
+    ///         "# A person.\nid: 42 # unique\nname: John Doe\n"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "yaml-edit")]
+    fn update_yaml_file<F, R>(filename: impl AsRef<Path>, function: F) -> crate::Result<R>
+    where
+        Self: serde::de::DeserializeOwned,
+        F: FnOnce(&mut Self) -> R,
+    {
+        Self::update_yaml_file_with(
+            filename,
+            &crate::WriteOptions::default().atomic(true),
+            function,
+        )
+    }
+
+    /// Like [`ToYaml::update_yaml_file`], but writes the file using the given options.
+    ///
+    /// See [`Document::write_to_file_with`](crate::Document::write_to_file_with) for how the options apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read, parsed or written
+    /// or if the object cannot be serialized.
+    #[cfg(feature = "yaml-edit")]
+    fn update_yaml_file_with<F, R>(
+        filename: impl AsRef<Path>,
+        options: &crate::WriteOptions,
+        function: F,
+    ) -> crate::Result<R>
+    where
+        Self: serde::de::DeserializeOwned,
+        F: FnOnce(&mut Self) -> R,
+    {
+        crate::edit::update_file(filename.as_ref(), crate::Format::Yaml, options, function)
+    }
+
+    /// Asynchronously write object as YAML to a [writer](tokio::io::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_yaml_async<W>(&self, writer: W) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        let text = <Self as ToYaml>::to_yaml(self);
+        async move { crate::asynchronous::write_text(writer, &text?).await }
+    }
+
+    /// Asynchronously writes object as serialized YAML string to a file.
+    ///
+    /// The file is written atomically, see [`WriteOptions::atomic`](crate::WriteOptions::atomic).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    #[cfg(feature = "tokio")]
+    fn write_to_yaml_file_async(
+        &self,
+        filename: impl AsRef<Path> + Send,
+    ) -> impl Future<Output = crate::Result<()>> + Send {
+        let text = <Self as ToYaml>::to_yaml(self);
+        async move { crate::asynchronous::write_text_file(filename.as_ref(), &text?).await }
+    }
 }
@@ -1,9 +1,11 @@
-use std::ffi::OsStr;
+use std::fs::read;
 use std::path::Path;
 
 use serde::Deserialize;
 
-use crate::Error;
+use crate::Format;
+use crate::compression::{decode, sniff};
+use crate::formats::detect_content;
 
 /// Makes an object capable of reading itself from a file of a specified format.
 pub trait FromFile
@@ -12,6 +14,11 @@ where
 {
     /// Deserializes an object from a file dependent on its file extension.
     ///
+    /// If the last file extension denotes a supported compression, e.g. `data.json.gz`,
+    /// the file is decompressed and the format is taken from the preceding extension.
+    /// Files with an unknown or no format extension, e.g. `config.conf` or `config`, are detected by their content,
+    /// i.e. their compression by its magic bytes and their format by [`Format::from_content`].
+    ///
     /// # Errors
     ///
     /// Returns an [`Error`] if the deserialization fails.
@@ -78,26 +85,657 @@ where
     ///         }
     ///     );
     /// }
+    ///
+    /// // Read compressed files with the respective compression feature:
+    /// #[cfg(all(feature = "json", feature = "gzip"))]
+    /// {
+    ///     use serde_rw::FromFile;
+    ///     assert_eq!(
+    ///         Person::from_file("./tests/person.json.gz").unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    ///
+    /// // Files with an unknown or no extension are detected by their content:
+    /// #[cfg(all(feature = "json", feature = "gzip"))]
+    /// {
+    ///     use serde_rw::FromFile;
+    ///     let filename = std::env::temp_dir().join("serde_rw_from_file.conf");
+    ///     std::fs::copy("./tests/person.json.gz", &filename).unwrap();
+    ///     assert_eq!(
+    ///         Person::from_file(&filename).unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
     /// ```
     fn from_file(filename: impl AsRef<Path>) -> crate::Result<Self> {
-        let extension = filename
-            .as_ref()
-            .extension()
-            .map(OsStr::to_ascii_lowercase)
-            .ok_or(Error::NoFileExtensionsSpecified)?;
-
-        match extension.as_encoded_bytes() {
-            #[cfg(feature = "json")]
-            b"json" => <Self as crate::FromJson>::from_json_file(filename),
-            #[cfg(feature = "toml")]
-            b"toml" => <Self as crate::FromToml>::from_toml_file(filename),
-            #[cfg(feature = "xml")]
-            b"xml" => <Self as crate::FromXml>::from_xml_file(filename),
-            #[cfg(feature = "yaml")]
-            b"yml" | b"yaml" => <Self as crate::FromYaml>::from_yaml_file(filename),
-            _ => Err(Error::UnsupportedFileExtension(extension)),
-        }
+        from_bytes(filename.as_ref(), read(filename.as_ref())?)
     }
+
+    /// Deserializes an object from a file of the given format, regardless of its file extension.
+    ///
+    /// Compressed content is detected by a trailing compression extension or,
+    /// unless the file has a format extension, by its magic bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Format, FromFile};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "toml")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_from_file_as.conf");
+    ///     std::fs::write(&filename, "id = 1337\nname = \"John Doe\"\n").unwrap();
+    ///     assert_eq!(
+    ///         Person::from_file_as(&filename, Format::Toml).unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    /// ```
+    fn from_file_as(filename: impl AsRef<Path>, format: Format) -> crate::Result<Self> {
+        let filename = filename.as_ref();
+        let bytes = read(filename)?;
+        let compression = sniff(filename, &bytes);
+        format.deserialize(&decode(bytes, compression)?)
+    }
+
+    /// Deserializes an object from a file while holding a shared advisory lock.
+    ///
+    /// This waits for concurrent [`UpdateFile::update_file`](crate::UpdateFile::update_file) calls to finish,
+    /// see [`UpdateFile`](crate::UpdateFile) for how files are locked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be locked or the deserialization fails.
+    fn from_file_locked(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        let lock = crate::lock::shared_lock_file(filename.as_ref())?;
+        lock.lock_shared()?;
+        let value = Self::from_file(filename)?;
+        lock.unlock()?;
+        Ok(value)
+    }
+
+    /// Deserializes an object from a previous version of a file,
+    /// as kept by [`WriteOptions::backup`](crate::WriteOptions::backup).
+    ///
+    /// The number counts from the most recent version, which is `1`.
+    /// The format is determined by the file's extension, regardless of the backup's name.
+    /// To restore the previous version, write the object back to the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::BackupNotFound`](crate::Error::BackupNotFound) if there is no such version
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Backup, FromFile, ToFile, WriteOptions};
+    ///
+    /// #[cfg(feature = "json")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_restore_backup.json");
+    ///     let options = WriteOptions::default().backup(Backup::Numbered(2));
+    ///
+    ///     for id in 1..=3 {
+    ///         serde_json::json!({"id": id}).write_to_file_with(&filename, &options).unwrap();
+    ///     }
+    ///
+    ///     let previous = serde_json::Value::restore_backup(&filename, 1).unwrap();
+    ///     assert_eq!(previous, serde_json::json!({"id": 2}));
+    ///     assert_eq!(serde_json::Value::restore_backup(&filename, 2).unwrap()["id"], 1);
+    ///     assert!(serde_json::Value::restore_backup(&filename, 3).is_err());
+    ///
+    ///     previous.write_to_file(&filename).unwrap();
+    /// }
+    /// ```
+    fn restore_backup(filename: impl AsRef<Path>, number: usize) -> crate::Result<Self> {
+        let filename = filename.as_ref();
+        from_bytes(filename, read(crate::backup::find(filename, number)?)?)
+    }
+
+    /// Deserializes an object from a file and overrides its fields with environment variables.
+    ///
+    /// See [`EnvOverrides`](crate::EnvOverrides) for how variables are mapped onto fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    /// Errors caused by environment variables name the respective variable.
+    #[cfg(feature = "env")]
+    fn from_file_with_env(
+        filename: impl AsRef<Path>,
+        overrides: &crate::EnvOverrides,
+    ) -> crate::Result<Self> {
+        overrides.deserialize(serde_json::Value::from_file(filename)?)
+    }
+
+    /// Deserializes an object from a file and expands placeholders within its string values.
+    ///
+    /// See [`Interpolation`](crate::Interpolation) for the supported placeholders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization or the interpolation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{FromFile, Interpolation};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "yaml")]
+    /// {
+    ///     assert_eq!(
+    ///         Person::from_file_interpolated("./tests/person.yml", &Interpolation::new().strict(true))
+    ///             .unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "interpolate")]
+    fn from_file_interpolated(
+        filename: impl AsRef<Path>,
+        interpolation: &crate::Interpolation,
+    ) -> crate::Result<Self> {
+        interpolation.deserialize(serde_json::Value::from_file(filename)?)
+    }
+
+    /// Deserializes an object from a file and returns the keys of the file that the object did not use.
+    ///
+    /// Keys are reported with their full path, e.g. `servers[0].prot`,
+    /// along with the known field that was probably meant, if any.
+    /// This allows callers to warn about typos, which serde silently ignores.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    /// }
+    ///
+    /// #[cfg(all(feature = "strict", feature = "yaml"))]
+    /// {
+    ///     let (person, unknown_keys) = Person::from_file_with_unknown_keys("./tests/person.yml").unwrap();
+    ///     assert_eq!(person, Person { id: 1337 });
+    ///     assert_eq!(unknown_keys[0].path(), "name");
+    /// }
+    /// ```
+    #[cfg(feature = "strict")]
+    fn from_file_with_unknown_keys(
+        filename: impl AsRef<Path>,
+    ) -> crate::Result<(Self, Vec<crate::UnknownKey>)> {
+        let (format, text) = read_text(filename.as_ref())?;
+        format.deserialize_with_unknown_keys(&text)
+    }
+
+    /// Deserializes an object from a file and rejects keys of the file that the object did not use.
+    ///
+    /// See [`FromFile::from_file_with_unknown_keys`] for how unknown keys are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnknownKeys`](crate::Error::UnknownKeys) if the file contains unknown keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     #[serde(default)]
+    ///     names: Vec<String>,
+    /// }
+    ///
+    /// #[cfg(all(feature = "strict", feature = "toml"))]
+    /// {
+    ///     let error = Person::from_file_strict("./tests/person.toml").unwrap_err();
+    ///     assert!(matches!(
+    ///         &error,
+    ///         serde_rw::Error::UnknownKeys(keys) if keys[0].suggestion() == Some("names")
+    ///     ));
+    ///     assert_eq!(error.to_string(), "Unknown keys:\n  name (did you mean `names`?)");
+    /// }
+    /// ```
+    #[cfg(feature = "strict")]
+    fn from_file_strict(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        let (value, unknown_keys) = Self::from_file_with_unknown_keys(filename)?;
+        crate::strict::deny(unknown_keys)?;
+        Ok(value)
+    }
+
+    /// Deserializes an object from a file and returns the keys that occur more than once within the same map.
+    ///
+    /// Most parsers silently keep one of the values of duplicate keys.
+    /// Keys are reported with their full path and the lines of both occurrences.
+    /// TOML files are not checked, since the TOML parser rejects duplicate keys itself.
+    /// Within XML files, only attributes are checked, as repeated elements denote sequences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use serde_rw::FromFile;
+    ///
+    /// #[cfg(all(feature = "duplicate-keys", feature = "yaml"))]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_duplicate_keys.yml");
+    ///     std::fs::write(&filename, "http: 80\nhttps: 443\nhttp: 8080\n").unwrap();
+    ///
+    ///     let (ports, duplicate_keys) =
+    ///         BTreeMap::<String, u16>::from_file_with_duplicate_keys(&filename).unwrap();
+    ///     assert_eq!(ports["http"], 8080);
+    ///     assert_eq!(duplicate_keys[0].to_string(), "http (lines 1 and 3)");
+    /// }
+    /// ```
+    #[cfg(feature = "duplicate-keys")]
+    fn from_file_with_duplicate_keys(
+        filename: impl AsRef<Path>,
+    ) -> crate::Result<(Self, Vec<crate::DuplicateKey>)> {
+        let (format, text) = read_text(filename.as_ref())?;
+        let duplicate_keys = crate::duplicates::find(format, &text);
+        Ok((format.deserialize(&text)?, duplicate_keys))
+    }
+
+    /// Deserializes an object from a file and rejects keys that occur more than once within the same map.
+    ///
+    /// See [`FromFile::from_file_with_duplicate_keys`] for how duplicate keys are detected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::DuplicateKeys`](crate::Error::DuplicateKeys) if the file contains duplicate keys
+    /// and an [`Error`](crate::Error) if the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    ///
+    /// #[cfg(all(feature = "duplicate-keys", feature = "json"))]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_unique_keys.json");
+    ///     std::fs::write(&filename, "{\"server\": {\n  \"port\": 80,\n  \"port\": 8080\n}}").unwrap();
+    ///
+    ///     let error = serde_json::Value::from_file_unique_keys(&filename).unwrap_err();
+    ///     assert!(matches!(
+    ///         &error,
+    ///         serde_rw::Error::DuplicateKeys(keys) if keys[0].first_line() == 2 && keys[0].line() == 3
+    ///     ));
+    ///     assert_eq!(
+    ///         error.to_string(),
+    ///         "Duplicate keys:\n  server.port (lines 2 and 3)"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "duplicate-keys")]
+    fn from_file_unique_keys(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        // Check before deserializing, since some parsers reject certain duplicates without their lines.
+        let (format, text) = read_text(filename.as_ref())?;
+        crate::duplicates::deny(crate::duplicates::find(format, &text))?;
+        format.deserialize(&text)
+    }
+
+    /// Deserializes an object from the value at a path within a file.
+    ///
+    /// Paths starting with a slash are JSON Pointers, e.g. `/package/metadata/myapp`,
+    /// which allow keys containing dots. Other paths consist of map keys separated by dots
+    /// and sequence indices in brackets, e.g. `package.metadata.myapp` or `servers[0]`.
+    /// Only the value at the path is deserialized into the object,
+    /// so the remainder of the file may hold arbitrary content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::PathNotFound`](crate::Error::PathNotFound) if the file does not contain the path
+    /// and an [`Error`](crate::Error) if the path is invalid or the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "pointer", feature = "toml"))]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_from_file_at.toml");
+    ///     std::fs::write(
+    ///         &filename,
+    ///         "[package]\nname = \"myapp\"\n\n[package.metadata.myapp]\nid = 1337\nname = \"John Doe\"\n",
+    ///     )
+    ///     .unwrap();
+    ///
+    ///     let person = Person {
+    ///         id: 1337,
+    ///         name: "John Doe".to_string(),
+    ///     };
+    ///     assert_eq!(Person::from_file_at(&filename, "package.metadata.myapp").unwrap(), person);
+    ///     assert_eq!(Person::from_file_at(&filename, "/package/metadata/myapp").unwrap(), person);
+    ///     assert_eq!(
+    ///         Person::from_file_at(&filename, "tool.myapp").unwrap_err().to_string(),
+    ///         "Path not found: tool.myapp"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "pointer")]
+    fn from_file_at(filename: impl AsRef<Path>, path: &str) -> crate::Result<Self> {
+        crate::path::from_file_at(filename.as_ref(), path)?
+            .ok_or_else(|| crate::Error::PathNotFound(path.to_string()))
+    }
+
+    /// Deserializes an object from the value at a path within a file
+    /// or returns the given default if the file does not contain the path.
+    ///
+    /// See [`FromFile::from_file_at`] for the supported paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the path is invalid or the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    ///
+    /// #[cfg(all(feature = "pointer", feature = "json"))]
+    /// {
+    ///     assert_eq!(u32::from_file_at_or("./tests/person.json", "/id", 0).unwrap(), 1337);
+    ///     assert_eq!(u32::from_file_at_or("./tests/person.json", "/age", 42).unwrap(), 42);
+    /// }
+    /// ```
+    #[cfg(feature = "pointer")]
+    fn from_file_at_or(
+        filename: impl AsRef<Path>,
+        path: &str,
+        default: Self,
+    ) -> crate::Result<Self> {
+        Ok(crate::path::from_file_at(filename.as_ref(), path)?.unwrap_or(default))
+    }
+
+    /// Deserializes an object from the value at a path within a file
+    /// or returns its default if the file does not contain the path.
+    ///
+    /// See [`FromFile::from_file_at`] for the supported paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the path is invalid or the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    ///
+    /// #[cfg(all(feature = "pointer", feature = "yaml"))]
+    /// {
+    ///     let names = Vec::<String>::from_file_at_or_default("./tests/person.yml", "aliases").unwrap();
+    ///     assert!(names.is_empty());
+    /// }
+    /// ```
+    #[cfg(feature = "pointer")]
+    fn from_file_at_or_default(filename: impl AsRef<Path>, path: &str) -> crate::Result<Self>
+    where
+        Self: Default,
+    {
+        Ok(crate::path::from_file_at(filename.as_ref(), path)?.unwrap_or_default())
+    }
+
+    /// Deserializes an object from a file and checks its constraints afterwards.
+    ///
+    /// See [`Validate`](crate::Validate) for how constraints are defined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Validation`](crate::Error::Validation) holding every violation
+    /// if the object violates its constraints and an [`Error`](crate::Error) if the deserialization fails.
+    fn from_file_checked(filename: impl AsRef<Path>) -> crate::Result<Self>
+    where
+        Self: crate::Validate,
+    {
+        let value = Self::from_file(filename)?;
+        value.check()?;
+        Ok(value)
+    }
+
+    /// Deserializes an object from a file after validating its content against a JSON Schema.
+    ///
+    /// The content is validated regardless of the file's format, so the same schema applies
+    /// to e.g. JSON, TOML and YAML files. TOML datetimes are validated as strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SchemaViolations`](crate::Error::SchemaViolations) holding the path to each violating value
+    /// if the content does not conform to the schema
+    /// and an [`Error`](crate::Error) if the schema is invalid or the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Error, FromFile};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "schema", feature = "toml"))]
+    /// {
+    ///     let schema = serde_json::json!({
+    ///         "type": "object",
+    ///         "properties": {"id": {"type": "integer"}, "name": {"type": "string"}},
+    ///         "required": ["id", "name"],
+    ///     });
+    ///     assert_eq!(
+    ///         Person::from_file_validated("./tests/person.toml", &schema).unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    ///
+    ///     let schema = serde_json::json!({"properties": {"id": {"maximum": 1000}}});
+    ///     let error = Person::from_file_validated("./tests/person.toml", &schema).unwrap_err();
+    ///     assert_eq!(
+    ///         error.to_string(),
+    ///         "Schema validation failed:\n  /id: 1337 is greater than the maximum of 1000"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "schema")]
+    fn from_file_validated(
+        filename: impl AsRef<Path>,
+        schema: &serde_json::Value,
+    ) -> crate::Result<Self> {
+        let value = serde_json::Value::from_file(filename)?;
+        crate::schema::validate_schema(&value, schema)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Deserializes an object from a file and resolves include directives within it.
+    ///
+    /// Objects with an `$include` key are replaced by the deep-merged content of the included files,
+    /// which is then overridden by the object's remaining keys.
+    /// The key's value is a path or glob pattern, or an array thereof, relative to the including file.
+    /// Wildcards only apply to the pattern itself, not to the including file's directory.
+    /// Within YAML files, values tagged with `!include` are replaced by the included file's content.
+    /// Included files may be of any supported format and may include further files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the deserialization fails.
+    /// Returns an [`Error::InvalidPattern`](crate::Error::InvalidPattern) if a glob pattern is invalid
+    /// or the including file's directory is not valid UTF-8.
+    /// Errors within included files and include cycles yield an
+    /// [`Error::Include`](crate::Error::Include) holding the chain of including files.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "include", feature = "toml", feature = "yaml"))]
+    /// {
+    ///     assert_eq!(
+    ///         Person::from_file_with_includes("./tests/include/person.yml").unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "include")]
+    fn from_file_with_includes(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(serde_json::from_value(crate::include::load(
+            filename.as_ref(),
+        )?)?)
+    }
+
+    /// Deserializes an object from an entry of a ZIP or (compressed) TAR archive.
+    ///
+    /// The format of the entry is determined by the entry's file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the archive cannot be read,
+    /// the entry does not exist or the deserialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "zip", feature = "toml"))]
+    /// {
+    ///     assert_eq!(
+    ///         Person::from_archive("./tests/people.zip", "people/person.toml").unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    ///
+    /// #[cfg(all(feature = "tar", feature = "gzip", feature = "yaml"))]
+    /// {
+    ///     assert_eq!(
+    ///         Person::from_archive("./tests/people.tar.gz", "people/person.yml").unwrap(),
+    ///         Person {
+    ///             id: 1337,
+    ///             name: "John Doe".to_string(),
+    ///         }
+    ///     );
+    /// }
+    /// ```
+    #[cfg(any(feature = "zip", feature = "tar"))]
+    fn from_archive(archive: impl AsRef<Path>, entry: impl AsRef<Path>) -> crate::Result<Self> {
+        crate::archive::read_entry(archive.as_ref(), entry.as_ref())
+    }
+
+    /// Deserializes all entries of a ZIP or (compressed) TAR archive that match a glob pattern.
+    ///
+    /// The format of each entry is determined by the entry's file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the archive cannot be read or the pattern is invalid.
+    /// The returned iterator yields an [`Error`](crate::Error) for each entry that cannot be deserialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::FromFile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "zip", feature = "json", feature = "toml", feature = "yaml"))]
+    /// {
+    ///     let people = Person::from_archive_glob("./tests/people.zip", "people/person.*")
+    ///         .unwrap()
+    ///         .collect::<Result<Vec<_>, _>>()
+    ///         .unwrap();
+    ///     assert_eq!(people.len(), 3);
+    /// }
+    /// ```
+    #[cfg(any(feature = "zip", feature = "tar"))]
+    fn from_archive_glob(
+        archive: impl AsRef<Path>,
+        pattern: &str,
+    ) -> crate::Result<crate::ArchiveEntries<Self>> {
+        crate::archive::read_entries(archive.as_ref(), pattern)
+    }
+}
+
+/// Deserializes an object from raw file content dependent on the given file name's extension.
+pub fn from_bytes<T>(filename: &Path, bytes: Vec<u8>) -> crate::Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let (format, text) = detect_content(filename, bytes)?;
+    format.deserialize(&text)
+}
+
+/// Reads the decompressed text of a file along with its format.
+#[cfg(any(feature = "strict", feature = "duplicate-keys"))]
+fn read_text(filename: &Path) -> crate::Result<(Format, String)> {
+    detect_content(filename, read(filename)?)
 }
 
 impl<T> FromFile for T where T: for<'de> Deserialize<'de> {}
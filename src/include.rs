@@ -0,0 +1,213 @@
+use std::fs::{canonicalize, read};
+use std::path::{Path, PathBuf};
+
+use glob::{Pattern, glob_with};
+use serde_json::Value;
+
+#[cfg(feature = "yaml")]
+use crate::Format;
+use crate::formats::detect_content;
+use crate::layered::merge;
+use crate::{ArrayStrategy, Error};
+
+/// The reserved key denoting files to include.
+pub const INCLUDE_KEY: &str = "$include";
+
+/// The YAML tag denoting a file to include.
+#[cfg(feature = "yaml")]
+const INCLUDE_TAG: &str = "include";
+
+/// Loads the given file into a JSON value, resolving all include directives.
+///
+/// An object with an [`INCLUDE_KEY`] is replaced by the deep-merged content of the included files,
+/// which is then overridden by the object's remaining keys.
+/// The directive's value is either a path or glob pattern, or an array thereof,
+/// which are resolved relative to the including file.
+/// In YAML files, values tagged with `!include` are replaced by the included file's content.
+pub fn load(path: &Path) -> crate::Result<Value> {
+    let mut chain = Vec::new();
+    load_chained(path, &mut chain)
+}
+
+/// Loads the given file, with `chain` holding the canonical paths of all files including it.
+fn load_chained(path: &Path, chain: &mut Vec<PathBuf>) -> crate::Result<Value> {
+    let canonical = canonicalize(path).map_err(|error| {
+        if chain.is_empty() {
+            error.into()
+        } else {
+            include_error(chain, path, error)
+        }
+    })?;
+
+    if chain.contains(&canonical) {
+        return Err(include_error(chain, path, "include cycle detected"));
+    }
+
+    chain.push(canonical);
+    let result = parse(path)
+        .map_err(|error| wrap(chain, error))
+        .and_then(|mut value| {
+            let directory = path.parent().unwrap_or_else(|| Path::new(""));
+            resolve(&mut value, directory, chain).map(|()| value)
+        });
+    chain.pop();
+    result
+}
+
+/// Reads a file into a JSON value, converting YAML `!include` tags into include directives.
+fn parse(path: &Path) -> crate::Result<Value> {
+    let (format, text) = detect_content(path, read(path)?)?;
+
+    #[cfg(feature = "yaml")]
+    if format == Format::Yaml {
+        return Ok(serde_json::to_value(untag(serde_yaml::from_str(&text)?))?);
+    }
+
+    format.deserialize(&text)
+}
+
+/// Replaces include directives within the given value by the content of the included files.
+fn resolve(value: &mut Value, directory: &Path, chain: &mut Vec<PathBuf>) -> crate::Result<()> {
+    match value {
+        Value::Object(object) => {
+            if let Some(directive) = object.remove(INCLUDE_KEY) {
+                let mut merged = Value::Null;
+
+                for path in paths(&directive, directory).map_err(|error| wrap(chain, error))? {
+                    merge(
+                        &mut merged,
+                        load_chained(&path, chain)?,
+                        &ArrayStrategy::default(),
+                    );
+                }
+
+                for element in object.values_mut() {
+                    resolve(element, directory, chain)?;
+                }
+
+                if !object.is_empty() {
+                    merge(
+                        &mut merged,
+                        Value::Object(std::mem::take(object)),
+                        &ArrayStrategy::default(),
+                    );
+                }
+
+                *value = merged;
+            } else {
+                for element in object.values_mut() {
+                    resolve(element, directory, chain)?;
+                }
+            }
+        }
+        Value::Array(array) => {
+            for element in array {
+                resolve(element, directory, chain)?;
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+
+    Ok(())
+}
+
+/// Returns the files denoted by an include directive, expanding glob patterns.
+fn paths(directive: &Value, directory: &Path) -> crate::Result<Vec<PathBuf>> {
+    let patterns = match directive {
+        Value::String(pattern) => vec![pattern.as_str()],
+        Value::Array(patterns) => patterns
+            .iter()
+            .map(|pattern| pattern.as_str().ok_or_else(|| invalid_directive(directive)))
+            .collect::<crate::Result<_>>()?,
+        _ => return Err(invalid_directive(directive)),
+    };
+    let mut paths = Vec::new();
+
+    for pattern in patterns {
+        let path = directory.join(pattern);
+
+        if Pattern::escape(pattern) == pattern {
+            paths.push(path);
+            continue;
+        }
+
+        // Only the pattern may contain wildcards, so the directory is matched literally.
+        let directory = directory.to_str().ok_or_else(|| {
+            Error::InvalidPattern(
+                format!(
+                    "cannot expand {pattern} within {}, which is not valid UTF-8",
+                    directory.display()
+                )
+                .into(),
+            )
+        })?;
+        let pattern = Path::new(&Pattern::escape(directory)).join(pattern);
+        for entry in glob_with(&pattern.to_string_lossy(), glob::MatchOptions::new())
+            .map_err(|error| Error::InvalidPattern(Box::new(error)))?
+        {
+            paths.push(entry.map_err(|error| Error::Io(error.into()))?);
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Converts values tagged with `!include` into include directives.
+#[cfg(feature = "yaml")]
+fn untag(value: serde_yaml::Value) -> serde_yaml::Value {
+    match value {
+        serde_yaml::Value::Tagged(tagged) if tagged.tag == INCLUDE_TAG => {
+            let mut directive = serde_yaml::Mapping::new();
+            directive.insert(INCLUDE_KEY.into(), untag(tagged.value));
+            serde_yaml::Value::Mapping(directive)
+        }
+        serde_yaml::Value::Tagged(mut tagged) => {
+            tagged.value = untag(tagged.value);
+            serde_yaml::Value::Tagged(tagged)
+        }
+        serde_yaml::Value::Sequence(sequence) => {
+            serde_yaml::Value::Sequence(sequence.into_iter().map(untag).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => serde_yaml::Value::Mapping(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (key, untag(value)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn invalid_directive(directive: &Value) -> Error {
+    Error::Serde(
+        format!("{INCLUDE_KEY} expects a path or an array of paths, got {directive}").into(),
+    )
+}
+
+/// Wraps an error that occurred within the last file of the chain, unless it has been wrapped already.
+///
+/// Errors within the including file itself are returned unchanged.
+fn wrap(chain: &[PathBuf], error: Error) -> Error {
+    match error {
+        Error::Include { .. } => error,
+        error if chain.len() < 2 => error,
+        error => Error::Include {
+            chain: chain.to_vec(),
+            source: Box::new(error),
+        },
+    }
+}
+
+/// Creates an error for a file that cannot be included into the last file of the chain.
+fn include_error(
+    chain: &[PathBuf],
+    path: &Path,
+    source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> Error {
+    let mut chain = chain.to_vec();
+    chain.push(path.to_path_buf());
+    Error::Include {
+        chain,
+        source: source.into(),
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::env::vars_os;
+use std::fs::read_to_string;
+
+use serde_json::Value;
+
+use crate::{Error, FromFile};
+
+const FILE_PREFIX: &str = "file:";
+const DEFAULT_SEPARATOR: &str = ":-";
+
+/// Expands placeholders within string values loaded from files.
+///
+/// The following placeholders are supported:
+///
+/// * `${VAR}` expands to the value of the variable `VAR`.
+/// * `${VAR:-default}` expands to `default` if `VAR` is undefined or empty.
+/// * `${file:/path/to/file}` expands to the content of the given file without a trailing line break.
+///
+/// `$${` is expanded to a literal `${`.
+/// Only string values are interpolated, keys are left untouched.
+///
+/// If a string consists of a single placeholder which expands to a number or a boolean,
+/// the value is deserialized as such if the target type expects it,
+/// so that e.g. `port: ${PORT}` can be deserialized into an integer.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{FromFile, Interpolation};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, Eq, PartialEq)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let interpolation = Interpolation::new().vars([("HOST", "example.com"), ("PORT", "8080")]);
+///
+/// #[cfg(feature = "json")]
+/// {
+///     assert_eq!(
+///         interpolation
+///             .deserialize::<Server>(serde_json::json!({"host": "${HOST}", "port": "${PORT}"}))
+///             .unwrap(),
+///         Server {
+///             host: "example.com".to_string(),
+///             port: 8080,
+///         }
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Interpolation {
+    vars: Option<HashMap<String, String>>,
+    strict: bool,
+}
+
+impl Interpolation {
+    /// Creates an interpolation which uses the process' environment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses the given variables instead of the process' environment.
+    #[must_use]
+    pub fn vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.vars = Some(
+            vars.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets whether undefined variables without a default value are an error.
+    ///
+    /// Otherwise, they expand to an empty string. Defaults to `false`.
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Expands all placeholders within the string values of the given value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Interpolation`] if a placeholder is malformed, a file cannot be read
+    /// or, in strict mode, a variable is undefined.
+    pub fn apply(&self, value: &mut Value) -> crate::Result<()> {
+        self.apply_tracked(value).map(drop)
+    }
+
+    /// Expands all placeholders within the string values of the given value and deserializes it into an object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Interpolation`] if the placeholders cannot be expanded
+    /// and an [`Error::Serde`] if the deserialization fails.
+    pub fn deserialize<T>(&self, mut value: Value) -> crate::Result<T>
+    where
+        T: FromFile,
+    {
+        let mut typed = self.apply_tracked(&mut value)?;
+
+        loop {
+            match serde_path_to_error::deserialize(value.clone()) {
+                Ok(object) => return Ok(object),
+                Err(error) => {
+                    let path = error.path().to_string();
+
+                    // Fall back to the expanded string if the target does not expect a typed value.
+                    let Some(index) = typed
+                        .iter()
+                        .position(|placeholder| placeholder.path == path)
+                    else {
+                        return Err(error.into_inner().into());
+                    };
+
+                    let placeholder = typed.swap_remove(index);
+
+                    if let Some(target) = value.pointer_mut(&placeholder.pointer) {
+                        *target = Value::String(placeholder.text);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expands all placeholders and returns the single-placeholder strings that were converted to typed values.
+    fn apply_tracked(&self, value: &mut Value) -> crate::Result<Vec<Typed>> {
+        let vars = self.vars.clone().unwrap_or_else(|| {
+            vars_os()
+                .filter_map(|(key, value)| {
+                    Some((key.into_string().ok()?, value.into_string().ok()?))
+                })
+                .collect()
+        });
+        let mut typed = Vec::new();
+        self.walk(value, &vars, &mut Location::default(), &mut typed)?;
+        Ok(typed)
+    }
+
+    fn walk(
+        &self,
+        value: &mut Value,
+        vars: &HashMap<String, String>,
+        location: &mut Location,
+        typed: &mut Vec<Typed>,
+    ) -> crate::Result<()> {
+        match value {
+            Value::String(text) => {
+                let single = is_single_placeholder(text);
+                let expanded = self.expand(text, vars)?;
+
+                match serde_json::from_str::<Value>(&expanded) {
+                    Ok(parsed @ (Value::Bool(_) | Value::Number(_))) if single => {
+                        typed.push(Typed {
+                            path: location.path.clone(),
+                            pointer: location.pointer.clone(),
+                            text: expanded,
+                        });
+                        *value = parsed;
+                    }
+                    _ => *text = expanded,
+                }
+            }
+            Value::Array(array) => {
+                for (index, element) in array.iter_mut().enumerate() {
+                    let segment = format!("[{index}]");
+                    location.enter(&segment, &index.to_string(), |location| {
+                        self.walk(element, vars, location, typed)
+                    })?;
+                }
+            }
+            Value::Object(object) => {
+                for (key, element) in object.iter_mut() {
+                    let segment = if location.path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!(".{key}")
+                    };
+                    let token = key.replace('~', "~0").replace('/', "~1");
+                    location.enter(&segment, &token, |location| {
+                        self.walk(element, vars, location, typed)
+                    })?;
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Expands all placeholders within the given text.
+    fn expand(&self, text: &str, vars: &HashMap<String, String>) -> crate::Result<String> {
+        let mut expanded = String::with_capacity(text.len());
+        let mut remainder = text;
+
+        while let Some((before, rest)) = remainder.split_once('$') {
+            expanded.push_str(before);
+
+            if let Some(rest) = rest.strip_prefix("${") {
+                expanded.push_str("${");
+                remainder = rest;
+            } else if let Some(rest) = rest.strip_prefix('{') {
+                let Some((placeholder, rest)) = rest.split_once('}') else {
+                    return Err(Error::Interpolation {
+                        placeholder: rest.to_string(),
+                        source: "unterminated placeholder".into(),
+                    });
+                };
+                expanded.push_str(&self.resolve(placeholder, vars)?);
+                remainder = rest;
+            } else {
+                expanded.push('$');
+                remainder = rest;
+            }
+        }
+
+        expanded.push_str(remainder);
+        Ok(expanded)
+    }
+
+    /// Resolves the content of a single placeholder.
+    fn resolve(&self, placeholder: &str, vars: &HashMap<String, String>) -> crate::Result<String> {
+        if let Some(path) = placeholder.strip_prefix(FILE_PREFIX) {
+            return read_to_string(path)
+                .map(|content| content.trim_end_matches(['\r', '\n']).to_string())
+                .map_err(|error| Error::Interpolation {
+                    placeholder: placeholder.to_string(),
+                    source: error.into(),
+                });
+        }
+
+        let (name, default) = match placeholder.split_once(DEFAULT_SEPARATOR) {
+            Some((name, default)) => (name, Some(default)),
+            None => (placeholder, None),
+        };
+
+        match (vars.get(name), default) {
+            (Some(value), Some(default)) if value.is_empty() => Ok(default.to_string()),
+            (Some(value), _) => Ok(value.clone()),
+            (None, Some(default)) => Ok(default.to_string()),
+            (None, None) if self.strict => Err(Error::Interpolation {
+                placeholder: placeholder.to_string(),
+                source: "undefined variable".into(),
+            }),
+            (None, None) => Ok(String::new()),
+        }
+    }
+}
+
+/// A single-placeholder string that was converted to a typed value.
+struct Typed {
+    path: String,
+    pointer: String,
+    text: String,
+}
+
+/// The location of a value within the interpolated value.
+#[derive(Default)]
+struct Location {
+    path: String,
+    pointer: String,
+}
+
+impl Location {
+    /// Runs the given function with the location extended by the given segments.
+    fn enter<F>(&mut self, segment: &str, token: &str, function: F) -> crate::Result<()>
+    where
+        F: FnOnce(&mut Self) -> crate::Result<()>,
+    {
+        let (path, pointer) = (self.path.len(), self.pointer.len());
+        self.path.push_str(segment);
+        self.pointer.push('/');
+        self.pointer.push_str(token);
+        let result = function(self);
+        self.path.truncate(path);
+        self.pointer.truncate(pointer);
+        result
+    }
+}
+
+/// Determines whether the text consists of exactly one placeholder.
+fn is_single_placeholder(text: &str) -> bool {
+    text.strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .is_some_and(|inner| !inner.contains('}'))
+}
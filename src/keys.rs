@@ -0,0 +1,85 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A key of the input that the deserialized type did not use.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct UnknownKey {
+    path: String,
+    suggestion: Option<String>,
+}
+
+impl UnknownKey {
+    /// Creates an unknown key at the given path with the known field that was probably meant, if any.
+    #[must_use]
+    pub const fn new(path: String, suggestion: Option<String>) -> Self {
+        Self { path, suggestion }
+    }
+
+    /// Returns the full path of the key, e.g. `servers[0].prot`.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the field of the struct holding the key that was probably meant, if any is similar to the key.
+    #[must_use]
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl Display for UnknownKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.suggestion {
+            Some(suggestion) => write!(f, "{} (did you mean `{suggestion}`?)", self.path),
+            None => write!(f, "{}", self.path),
+        }
+    }
+}
+
+/// A key that occurs more than once within the same map.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DuplicateKey {
+    path: String,
+    first_line: usize,
+    line: usize,
+}
+
+impl DuplicateKey {
+    /// Creates a duplicate key at the given path with the lines of its first and repeated occurrence.
+    #[must_use]
+    pub const fn new(path: String, first_line: usize, line: usize) -> Self {
+        Self {
+            path,
+            first_line,
+            line,
+        }
+    }
+
+    /// Returns the full path of the key, e.g. `servers[0].port`.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the line of the key's first occurrence.
+    #[must_use]
+    pub const fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    /// Returns the line of the key's repeated occurrence.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for DuplicateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (lines {} and {})",
+            self.path, self.first_line, self.line
+        )
+    }
+}
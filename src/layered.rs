@@ -0,0 +1,321 @@
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Error, Format, FromFile};
+
+/// Determines which source takes precedence when merging layers.
+///
+/// # Examples
+/// ```
+/// use serde_json::json;
+/// use serde_rw::{Layered, Precedence};
+///
+/// let layered = Layered::new()
+///     .value(json!({"host": "localhost", "port": 80}))
+///     .value(json!({"port": 8080}));
+/// assert_eq!(
+///     layered.load_value().unwrap(),
+///     json!({"host": "localhost", "port": 8080})
+/// );
+/// assert_eq!(
+///     layered.precedence(Precedence::FirstWins).load_value().unwrap(),
+///     json!({"host": "localhost", "port": 80})
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Precedence {
+    /// Sources added later override sources added earlier.
+    #[default]
+    LastWins,
+    /// Sources added earlier override sources added later.
+    FirstWins,
+}
+
+/// Determines how arrays are merged when merging layers.
+///
+/// # Examples
+/// ```
+/// use serde_json::json;
+/// use serde_rw::{ArrayStrategy, Layered};
+///
+/// let layered = || {
+///     Layered::new()
+///         .value(json!({"servers": [{"name": "a", "port": 80}, {"name": "b", "port": 81}]}))
+///         .value(json!({"servers": [{"name": "b", "port": 8081}, {"name": "c", "port": 82}]}))
+/// };
+/// assert_eq!(
+///     layered().load_value().unwrap(),
+///     json!({"servers": [{"name": "b", "port": 8081}, {"name": "c", "port": 82}]})
+/// );
+/// assert_eq!(
+///     layered().arrays(ArrayStrategy::Append).load_value().unwrap(),
+///     json!({"servers": [
+///         {"name": "a", "port": 80},
+///         {"name": "b", "port": 81},
+///         {"name": "b", "port": 8081},
+///         {"name": "c", "port": 82},
+///     ]})
+/// );
+/// assert_eq!(
+///     layered()
+///         .arrays(ArrayStrategy::MergeByKey("name".to_string()))
+///         .load_value()
+///         .unwrap(),
+///     json!({"servers": [
+///         {"name": "a", "port": 80},
+///         {"name": "b", "port": 8081},
+///         {"name": "c", "port": 82},
+///     ]})
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ArrayStrategy {
+    /// Arrays of the overriding source replace arrays of the overridden source.
+    #[default]
+    Replace,
+    /// Arrays of the overriding source are appended to arrays of the overridden source.
+    Append,
+    /// Objects within arrays are merged if their values at the given key are equal.
+    ///
+    /// Elements without a matching counterpart are appended.
+    MergeByKey(String),
+}
+
+/// A source of a configuration layer.
+enum Source {
+    File(PathBuf),
+    OptionalFile(PathBuf),
+    String(String, Format),
+    Value(Value),
+    Default(fn() -> crate::Result<Value>),
+}
+
+impl Source {
+    /// Loads the source, returning `None` if an optional file does not exist.
+    fn load(&self) -> crate::Result<Option<Value>> {
+        match self {
+            Self::File(path) => Value::from_file(path).map(Some),
+            Self::OptionalFile(path) => match Value::from_file(path) {
+                Ok(value) => Ok(Some(value)),
+                Err(Error::Io(error)) if error.kind() == ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(error),
+            },
+            Self::String(text, format) => format.deserialize(text).map(Some),
+            Self::Value(value) => Ok(Some(value.clone())),
+            Self::Default(default) => default().map(Some),
+        }
+    }
+}
+
+impl Debug for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::OptionalFile(path) => f.debug_tuple("OptionalFile").field(path).finish(),
+            Self::String(text, format) => {
+                f.debug_tuple("String").field(text).field(format).finish()
+            }
+            Self::Value(value) => f.debug_tuple("Value").field(value).finish(),
+            Self::Default(_) => f.write_str("Default"),
+        }
+    }
+}
+
+/// Loads an object from several deep-merged sources.
+///
+/// All sources are loaded into an intermediate value first, which is then deserialized into the target type.
+/// Thus, fields only override other sources if they are explicitly set within a source.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Format, Layered};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+/// struct Person {
+///     id: u32,
+///     name: String,
+///     #[serde(default)]
+///     tags: Vec<String>,
+/// }
+///
+/// #[cfg(feature = "toml")]
+/// {
+///     let person: Person = Layered::new()
+///         .defaults::<Person>()
+///         .file("./tests/person.json")
+///         .optional_file("./tests/nonexistent.yml")
+///         .string(r#"name = "Jane Doe""#, Format::Toml)
+///         .load()
+///         .unwrap();
+///     assert_eq!(
+///         person,
+///         Person {
+///             id: 1337,
+///             name: "Jane Doe".to_string(),
+///             tags: Vec::new(),
+///         }
+///     );
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Layered {
+    sources: Vec<Source>,
+    precedence: Precedence,
+    arrays: ArrayStrategy,
+}
+
+impl Layered {
+    /// Creates a new loader without any sources.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, which must exist, as a source.
+    ///
+    /// The format is determined by the file extension, see [`FromFile::from_file`].
+    #[must_use]
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(Source::File(path.into()));
+        self
+    }
+
+    /// Adds a file, which is skipped if it does not exist, as a source.
+    ///
+    /// The format is determined by the file extension, see [`FromFile::from_file`].
+    #[must_use]
+    pub fn optional_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(Source::OptionalFile(path.into()));
+        self
+    }
+
+    /// Adds a string in the given format as a source.
+    #[must_use]
+    pub fn string(mut self, text: impl Into<String>, format: Format) -> Self {
+        self.sources.push(Source::String(text.into(), format));
+        self
+    }
+
+    /// Adds a JSON value as a source.
+    #[must_use]
+    pub fn value(mut self, value: Value) -> Self {
+        self.sources.push(Source::Value(value));
+        self
+    }
+
+    /// Adds the [`Default`] of the given type as a source.
+    #[must_use]
+    pub fn defaults<T>(mut self) -> Self
+    where
+        T: Default + Serialize,
+    {
+        self.sources.push(Source::Default(default_value::<T>));
+        self
+    }
+
+    /// Sets which sources take precedence.
+    ///
+    /// Defaults to [`Precedence::LastWins`].
+    #[must_use]
+    pub const fn precedence(mut self, precedence: Precedence) -> Self {
+        self.precedence = precedence;
+        self
+    }
+
+    /// Sets how arrays are merged.
+    ///
+    /// Defaults to [`ArrayStrategy::Replace`].
+    #[must_use]
+    pub fn arrays(mut self, strategy: ArrayStrategy) -> Self {
+        self.arrays = strategy;
+        self
+    }
+
+    /// Loads and merges all sources into an object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if any source cannot be loaded or the merged value cannot be deserialized.
+    pub fn load<T>(&self) -> crate::Result<T>
+    where
+        T: FromFile,
+    {
+        Ok(T::deserialize(self.load_value()?)?)
+    }
+
+    /// Loads and merges all sources into a JSON value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if any source cannot be loaded.
+    pub fn load_value(&self) -> crate::Result<Value> {
+        let mut merged = Value::Null;
+        let mut layers = Vec::with_capacity(self.sources.len());
+
+        for source in &self.sources {
+            if let Some(value) = source.load()? {
+                layers.push(value);
+            }
+        }
+
+        if self.precedence == Precedence::FirstWins {
+            layers.reverse();
+        }
+
+        for layer in layers {
+            merge(&mut merged, layer, &self.arrays);
+        }
+
+        Ok(merged)
+    }
+}
+
+/// Deep-merges `overlay` into `base`.
+///
+/// Objects are merged recursively, arrays according to the given strategy
+/// and all other values of `overlay` replace those of `base`.
+pub fn merge(base: &mut Value, overlay: Value, arrays: &ArrayStrategy) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value, arrays),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(base), Value::Array(overlay)) => match arrays {
+            ArrayStrategy::Replace => *base = overlay,
+            ArrayStrategy::Append => base.extend(overlay),
+            ArrayStrategy::MergeByKey(key) => {
+                for element in overlay {
+                    let counterpart = element.get(key).and_then(|id| {
+                        base.iter_mut()
+                            .find(|existing| existing.get(key) == Some(id))
+                    });
+
+                    match counterpart {
+                        Some(existing) => merge(existing, element, arrays),
+                        None => base.push(element),
+                    }
+                }
+            }
+        },
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn default_value<T>() -> crate::Result<Value>
+where
+    T: Default + Serialize,
+{
+    Ok(serde_json::to_value(T::default())?)
+}
@@ -1,6 +1,23 @@
 //! A library to extend serde serializers and deserializers with the ability
 //! to read / write different file formats from / to files.
 
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub use archive::{ArchiveEntries, ArchiveWriter};
+#[cfg(feature = "tokio")]
+pub use asynchronous::{AsyncFromFile, AsyncToFile};
+pub use backup::Backup;
+// Only used by the `serde-rw` binary.
+#[cfg(feature = "cli")]
+use clap as _;
+pub use compression::Compression;
+#[cfg(feature = "diff")]
+pub use diff::{Change, ChangeKind, Diff, diff};
+#[cfg(feature = "discover")]
+pub use discover::{Discovered, Discovery, discover};
+#[cfg(feature = "edit")]
+pub use edit::Document;
+#[cfg(feature = "env")]
+pub use env::EnvOverrides;
 pub use error::Error;
 #[cfg(feature = "json")]
 pub use formats::json::{FromJson, ToJson};
@@ -10,13 +27,85 @@ pub use formats::toml::{FromToml, ToToml};
 pub use formats::xml::{FromXml, ToXml};
 #[cfg(feature = "yaml")]
 pub use formats::yaml::{FromYaml, ToYaml};
+pub use formats::{Format, Unrepresentable};
 pub use from_file::FromFile;
+#[cfg(feature = "interpolate")]
+pub use interpolate::Interpolation;
+pub use keys::{DuplicateKey, UnknownKey};
+#[cfg(feature = "layered")]
+pub use layered::{ArrayStrategy, Layered, Precedence};
+pub use lock::UpdateFile;
+#[cfg(feature = "patch")]
+pub use patch::{Patch, apply_patch_file, apply_patch_file_checked};
+pub use persisted::{LoadOrDefault, Persisted};
+#[cfg(feature = "schema")]
+pub use schema::{validate_schema, write_schema};
 pub use to_file::ToFile;
+#[cfg(feature = "transcode")]
+pub use transcode::{transcode, transcode_stream, transcode_with};
+pub use validate::{Validate, Violations};
+#[cfg(feature = "value")]
+pub use value::{Float, Integer, Map, Value};
+#[cfg(feature = "watch")]
+pub use watch::{Watched, WatchedBuilder};
+pub use write_options::{Comparison, WriteOptions};
+// Only used to detect duplicate keys within YAML files.
+#[cfg(all(feature = "duplicate-keys", not(feature = "yaml")))]
+use yaml_rust2 as _;
 
 /// Result type for this crate.
 pub type Result<T> = std::result::Result<T, Error>;
 
+#[cfg(any(feature = "zip", feature = "tar"))]
+mod archive;
+#[cfg(feature = "tokio")]
+mod asynchronous;
+mod backup;
+mod compression;
+#[cfg(feature = "diff")]
+mod diff;
+#[cfg(feature = "discover")]
+mod discover;
+#[cfg(feature = "duplicate-keys")]
+#[cfg_attr(
+    not(any(feature = "json", feature = "xml", feature = "yaml")),
+    allow(dead_code, unused_imports)
+)]
+mod duplicates;
+#[cfg(feature = "edit")]
+mod edit;
+#[cfg(feature = "env")]
+mod env;
 mod error;
 mod formats;
 mod from_file;
+#[cfg(feature = "include")]
+mod include;
+#[cfg(feature = "interpolate")]
+mod interpolate;
+mod keys;
+#[cfg(feature = "layered")]
+mod layered;
+mod lock;
+#[cfg(feature = "patch")]
+mod patch;
+#[cfg(any(feature = "value", feature = "edit", feature = "pointer"))]
+mod path;
+mod persisted;
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "strict")]
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(dead_code)
+)]
+mod strict;
 mod to_file;
+#[cfg(feature = "transcode")]
+mod transcode;
+mod validate;
+#[cfg(feature = "value")]
+mod value;
+#[cfg(feature = "watch")]
+mod watch;
+mod write_options;
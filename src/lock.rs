@@ -0,0 +1,112 @@
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use crate::{FromFile, ToFile, WriteOptions};
+
+/// Makes an object capable of updating its file under an exclusive advisory lock.
+///
+/// The lock is held on a sidecar file named `.<file name>.lock` next to the file,
+/// since writing the file atomically replaces it and thus would discard locks held on the file itself.
+/// The sidecar file is not removed afterwards, as doing so would race with other processes acquiring the lock.
+/// Shared locks open an existing sidecar file read-only. If it does not exist and cannot be created,
+/// e.g. within a read-only directory, they are held on the file itself, which cannot be written there anyway.
+/// Since the lock is advisory, it only protects against processes that use the same locking scheme,
+/// such as [`FromFile::from_file_locked`].
+pub trait UpdateFile: FromFile + ToFile {
+    /// Reads an object from a file, applies the given function to it and atomically writes it back
+    /// while holding an exclusive lock.
+    ///
+    /// The format is determined by the file extension, see [`FromFile::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be locked, read or written.
+    /// If the lock cannot be acquired, this blocks until it is released by the holding process.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{FromFile, ToFile, UpdateFile};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+    /// struct Counter {
+    ///     count: u32,
+    /// }
+    ///
+    /// #[cfg(feature = "json")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_update_file.json");
+    ///     Counter { count: 41 }.write_to_file(&filename).unwrap();
+    ///
+    ///     let count = Counter::update_file(&filename, |counter| {
+    ///         counter.count += 1;
+    ///         counter.count
+    ///     })
+    ///     .unwrap();
+    ///     assert_eq!(count, 42);
+    ///     assert_eq!(Counter::from_file_locked(&filename).unwrap().count, 42);
+    /// }
+    /// ```
+    fn update_file<F, R>(filename: impl AsRef<Path>, function: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        Self::update_file_with(filename, &WriteOptions::default(), function)
+    }
+
+    /// Like [`UpdateFile::update_file`], but writes the file using the given options.
+    ///
+    /// The file is always written atomically, regardless of the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be locked, read or written.
+    fn update_file_with<F, R>(
+        filename: impl AsRef<Path>,
+        options: &WriteOptions,
+        function: F,
+    ) -> crate::Result<R>
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let lock = lock_file(filename.as_ref())?;
+        lock.lock()?;
+        let mut value = Self::from_file(filename.as_ref())?;
+        let result = function(&mut value);
+        value.write_to_file_with(filename, &options.atomic(true))?;
+        lock.unlock()?;
+        Ok(result)
+    }
+}
+
+impl<T> UpdateFile for T where T: FromFile + ToFile {}
+
+/// Opens the sidecar lock file of the given file, creating it if necessary.
+pub fn lock_file(filename: &Path) -> crate::Result<File> {
+    Ok(OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(filename))?)
+}
+
+/// Opens the file to hold a shared lock on.
+///
+/// This is the sidecar lock file, if it exists or can be created, or the file itself otherwise,
+/// so that files within read-only directories can be read under a lock.
+pub fn shared_lock_file(filename: &Path) -> crate::Result<File> {
+    if let Ok(file) = File::open(lock_path(filename)) {
+        return Ok(file);
+    }
+
+    lock_file(filename).or_else(|_| Ok(File::open(filename)?))
+}
+
+/// Returns the path of the sidecar lock file of the given file.
+fn lock_path(filename: &Path) -> PathBuf {
+    let mut name = OsString::from(".");
+    name.push(filename.file_name().unwrap_or_default());
+    name.push(".lock");
+    filename.with_file_name(name)
+}
@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{FromFile, ToFile};
+
+/// The kind of a patch file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Patch {
+    /// A JSON Patch (RFC 6902), i.e. a list of operations.
+    ///
+    /// Since TOML files cannot hold a top-level array, the operations may also be given as
+    /// an array of tables named `operations`, i.e. `[[operations]]`, in any format.
+    Json,
+    /// A JSON Merge Patch (RFC 7386), i.e. a value that is merged into the target.
+    Merge,
+}
+
+/// Applies a patch file of the given kind onto a target file and writes the result back in the target's format.
+///
+/// Both the target and the patch may be written in any supported format,
+/// which is determined by their file extensions, see [`FromFile::from_file`].
+/// A JSON Patch is applied atomically, i.e. the target is left unchanged if any of its operations fails.
+///
+/// # Errors
+///
+/// Returns an [`Error::Patch`](crate::Error::Patch) if an operation of a JSON Patch fails
+/// and an [`Error`](crate::Error) if the files cannot be read, parsed or written
+/// or a JSON Patch is not a list of operations.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Patch, apply_patch_file};
+///
+/// #[cfg(all(feature = "patch", feature = "json", feature = "toml", feature = "yaml"))]
+/// {
+///     let target = std::env::temp_dir().join("serde_rw_apply_patch.json");
+///     let patch = std::env::temp_dir().join("serde_rw_apply_patch.yml");
+///     std::fs::write(&target, r#"{"id": 1337, "name": "John Doe"}"#).unwrap();
+///     std::fs::write(&patch, "- op: replace\n  path: /name\n  value: Jane Doe\n").unwrap();
+///
+///     apply_patch_file(&target, &patch, Patch::Json).unwrap();
+///     assert_eq!(
+///         std::fs::read_to_string(&target).unwrap(),
+///         "{\n  \"id\": 1337,\n  \"name\": \"Jane Doe\"\n}"
+///     );
+///
+///     let patch = std::env::temp_dir().join("serde_rw_apply_patch.toml");
+///     std::fs::write(&patch, "[[operations]]\nop = \"remove\"\npath = \"/name\"\n").unwrap();
+///     apply_patch_file(&target, &patch, Patch::Json).unwrap();
+///     assert_eq!(
+///         std::fs::read_to_string(&target).unwrap(),
+///         "{\n  \"id\": 1337\n}"
+///     );
+///
+///     let patch = std::env::temp_dir().join("serde_rw_apply_patch.merge.yml");
+///     std::fs::write(&patch, "name: John Doe\n").unwrap();
+///     apply_patch_file(&target, &patch, Patch::Merge).unwrap();
+///     assert_eq!(
+///         std::fs::read_to_string(&target).unwrap(),
+///         "{\n  \"id\": 1337,\n  \"name\": \"John Doe\"\n}"
+///     );
+/// }
+/// ```
+pub fn apply_patch_file(
+    target: impl AsRef<Path>,
+    patch: impl AsRef<Path>,
+    kind: Patch,
+) -> crate::Result<()> {
+    let target = target.as_ref();
+    write(target, &patched(target, patch.as_ref(), kind)?)
+}
+
+/// Applies a patch file onto a target file, if the result deserializes into the given type.
+///
+/// Returns the deserialized result. See [`apply_patch_file`] for how patches are applied.
+///
+/// # Errors
+///
+/// Returns an [`Error`](crate::Error) if the patch cannot be applied or the result does not deserialize
+/// into the given type, in which case the target is left unchanged.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Patch, apply_patch_file_checked};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, Eq, PartialEq)]
+/// struct Person {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[cfg(all(feature = "patch", feature = "toml"))]
+/// {
+///     let target = std::env::temp_dir().join("serde_rw_apply_patch_checked.toml");
+///     let patch = std::env::temp_dir().join("serde_rw_apply_patch_checked.merge.toml");
+///     std::fs::write(&target, "id = 1337\nname = \"John Doe\"\n").unwrap();
+///
+///     std::fs::write(&patch, "id = 42\n").unwrap();
+///     assert_eq!(
+///         apply_patch_file_checked::<Person>(&target, &patch, Patch::Merge).unwrap(),
+///         Person {
+///             id: 42,
+///             name: "John Doe".to_string(),
+///         }
+///     );
+///
+///     std::fs::write(&patch, "id = \"forty-two\"\n").unwrap();
+///     assert!(apply_patch_file_checked::<Person>(&target, &patch, Patch::Merge).is_err());
+///     assert_eq!(
+///         std::fs::read_to_string(&target).unwrap(),
+///         "id = 42\nname = \"John Doe\"\n"
+///     );
+/// }
+/// ```
+pub fn apply_patch_file_checked<T>(
+    target: impl AsRef<Path>,
+    patch: impl AsRef<Path>,
+    kind: Patch,
+) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let target = target.as_ref();
+    let value = patched(target, patch.as_ref(), kind)?;
+    let object = T::deserialize(&value)?;
+    write(target, &value)?;
+    Ok(object)
+}
+
+/// Reads the target file and applies the patch file onto its content.
+fn patched(target: &Path, patch: &Path, kind: Patch) -> crate::Result<Value> {
+    let mut value = Value::from_file(target)?;
+    apply(&mut value, Value::from_file(patch)?, kind)?;
+    Ok(value)
+}
+
+/// Writes the patched content to the target file in its format.
+fn write(target: &Path, value: &Value) -> crate::Result<()> {
+    // TOML datetimes appear as single-entry objects, which only TOML's own value type turns back into datetimes.
+    #[cfg(feature = "toml")]
+    if crate::formats::detect(target)?.0 == crate::Format::Toml {
+        return toml::Value::deserialize(value)?.write_to_file_pretty(target);
+    }
+
+    value.write_to_file_pretty(target)
+}
+
+/// Applies a patch of the given kind onto the value.
+fn apply(value: &mut Value, patch: Value, kind: Patch) -> crate::Result<()> {
+    match kind {
+        Patch::Json => {
+            json_patch::patch(value, &json_patch::Patch::deserialize(operations(patch))?)?;
+        }
+        Patch::Merge => json_patch::merge(value, &patch),
+    }
+
+    Ok(())
+}
+
+/// Returns the operations of a JSON Patch, which may be wrapped in a table as `[[operations]]`.
+fn operations(patch: Value) -> Value {
+    match patch {
+        Value::Object(mut object) if object.len() == 1 && object.contains_key("operations") => {
+            object.remove("operations").unwrap_or_default()
+        }
+        patch => patch,
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Write;
+#[cfg(feature = "pointer")]
+use std::path::Path;
+
+use crate::Error;
+
+/// A segment of a path.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Segment<'path> {
+    Key(&'path str),
+    Index(usize),
+}
+
+impl Segment<'_> {
+    pub fn index(self) -> Option<usize> {
+        match self {
+            Self::Key(key) => key.parse().ok(),
+            Self::Index(index) => Some(index),
+        }
+    }
+
+    pub fn key(self) -> String {
+        match self {
+            Self::Key(key) => key.to_string(),
+            Self::Index(index) => index.to_string(),
+        }
+    }
+}
+
+/// Splits a path into its segments.
+///
+/// Paths consist of map keys separated by dots and sequence indices in brackets, e.g. `servers[0].host`.
+pub fn segments(path: &str) -> crate::Result<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut indices) = part
+            .split_once('[')
+            .map_or((part, ""), |(key, indices)| (key, indices));
+
+        if !key.is_empty() {
+            segments.push(Segment::Key(key));
+        }
+
+        while !indices.is_empty() {
+            let Some((index, rest)) = indices.split_once(']') else {
+                return Err(Error::InvalidPath(format!("{path}: unterminated index")));
+            };
+            segments.push(Segment::Index(index.parse().map_err(|_| {
+                Error::InvalidPath(format!("{path}: invalid index {index:?}"))
+            })?));
+            indices = rest.strip_prefix('[').unwrap_or(rest);
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Joins segments into a path, as accepted by [`segments`].
+#[cfg_attr(not(any(feature = "edit", feature = "diff")), allow(dead_code))]
+pub fn join(segments: &[Segment<'_>]) -> String {
+    let mut path = String::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Key(key) if path.is_empty() => path.push_str(key),
+            Segment::Key(key) => {
+                path.push('.');
+                path.push_str(key);
+            }
+            Segment::Index(index) => {
+                let _ = write!(path, "[{index}]");
+            }
+        }
+    }
+
+    path
+}
+
+/// Joins segments into a JSON Pointer, escaping `~` and `/` within keys.
+#[cfg(feature = "diff")]
+pub fn pointer(segments: &[Segment<'_>]) -> String {
+    segments.iter().fold(String::new(), |mut pointer, segment| {
+        pointer.push('/');
+        pointer.push_str(&segment.key().replace('~', "~0").replace('/', "~1"));
+        pointer
+    })
+}
+
+#[cfg_attr(not(feature = "value"), allow(dead_code))]
+pub fn out_of_bounds(path: &str, index: usize) -> Error {
+    Error::InvalidPath(format!("{path}: index {index} out of bounds"))
+}
+
+/// Deserializes the value at a JSON Pointer, e.g. `/tool/myapp`, or a path, e.g. `tool.myapp`, within a file.
+///
+/// Returns `None` if the file does not contain the path.
+#[cfg(feature = "pointer")]
+pub fn from_file_at<T>(filename: &Path, path: &str) -> crate::Result<Option<T>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    use serde_json::Value;
+
+    use crate::FromFile;
+
+    let mut value = Value::from_file(filename)?;
+    let subtree = if path.starts_with('/') {
+        value.pointer_mut(path)
+    } else {
+        segments(path)?
+            .into_iter()
+            .try_fold(&mut value, |value, segment| match value {
+                Value::Array(array) => array.get_mut(segment.index()?),
+                Value::Object(object) => object.get_mut(&segment.key()),
+                _ => None,
+            })
+    };
+
+    Ok(subtree
+        .map(|subtree| serde_json::from_value(subtree.take()))
+        .transpose()?)
+}
@@ -0,0 +1,291 @@
+use std::fs::create_dir_all;
+use std::io::ErrorKind;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use crate::{Error, FromFile, ToFile, WriteOptions};
+
+/// Makes an object capable of loading itself from a file, which is created from its default if missing.
+pub trait LoadOrDefault: FromFile + ToFile + Default {
+    /// Deserializes an object from a file or, if the file does not exist,
+    /// writes the object's default to the file via [`ToFile::write_to_file_pretty`] and returns it.
+    ///
+    /// Missing parent directories are created.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read, deserialized or created.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::LoadOrDefault;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+    /// struct Settings {
+    ///     verbose: bool,
+    ///     retries: u32,
+    /// }
+    ///
+    /// #[cfg(feature = "toml")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_load_or_default/settings.toml");
+    ///     let _ = std::fs::remove_file(&filename);
+    ///
+    ///     assert_eq!(Settings::load_or_default(&filename).unwrap(), Settings::default());
+    ///     assert!(filename.exists());
+    /// }
+    /// ```
+    fn load_or_default(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        match Self::from_file(filename.as_ref()) {
+            Err(Error::Io(error)) if error.kind() == ErrorKind::NotFound => {
+                let value = Self::default();
+                create_parent_dir(filename.as_ref())?;
+                value.write_to_file_pretty(filename)?;
+                Ok(value)
+            }
+            result => result,
+        }
+    }
+
+    /// Like [`LoadOrDefault::load_or_default`], but additionally fills keys missing from an existing file
+    /// with their defaults and writes the result back, so that users find newly added options.
+    ///
+    /// Keys within the file that are unknown to the object are retained.
+    /// Defaults of `null`, e.g. of `Option` fields, are not filled in, since not all formats can represent them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read, deserialized or written.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{FromFile, LoadOrDefault};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+    /// struct Settings {
+    ///     verbose: bool,
+    ///     #[serde(default)]
+    ///     retries: u32,
+    /// }
+    ///
+    /// #[cfg(feature = "json")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_load_or_default_filled.json");
+    ///     std::fs::write(&filename, r#"{"verbose": true}"#).unwrap();
+    ///
+    ///     let settings = Settings::load_or_default_filled(&filename).unwrap();
+    ///     assert!(settings.verbose);
+    ///     assert_eq!(
+    ///         serde_json::Value::from_file(&filename).unwrap(),
+    ///         serde_json::json!({"verbose": true, "retries": 0})
+    ///     );
+    /// }
+    ///
+    /// #[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+    /// struct Server {
+    ///     name: String,
+    ///     #[serde(default)]
+    ///     port: Option<u16>,
+    ///     #[serde(default)]
+    ///     workers: u32,
+    /// }
+    ///
+    /// #[cfg(feature = "toml")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_load_or_default_filled.toml");
+    ///     std::fs::write(&filename, "name = \"x\"\n").unwrap();
+    ///
+    ///     let server = Server::load_or_default_filled(&filename).unwrap();
+    ///     assert_eq!(server.port, None);
+    ///     assert_eq!(
+    ///         std::fs::read_to_string(&filename).unwrap(),
+    ///         "name = \"x\"\nworkers = 0\n"
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "layered")]
+    fn load_or_default_filled(filename: impl AsRef<Path>) -> crate::Result<Self> {
+        let existing = match serde_json::Value::from_file(filename.as_ref()) {
+            Err(Error::Io(error)) if error.kind() == ErrorKind::NotFound => {
+                return Self::load_or_default(filename);
+            }
+            result => result?,
+        };
+        let mut filled = without_nulls(serde_json::to_value(Self::default())?);
+        crate::layered::merge(
+            &mut filled,
+            existing.clone(),
+            &crate::ArrayStrategy::default(),
+        );
+
+        if filled != existing {
+            filled
+                .write_to_file_with(filename, &WriteOptions::default().pretty(true).atomic(true))?;
+        }
+
+        Ok(serde_json::from_value(filled)?)
+    }
+}
+
+impl<T> LoadOrDefault for T where T: FromFile + ToFile + Default {}
+
+/// Removes all map entries holding `null`, recursively.
+#[cfg(feature = "layered")]
+fn without_nulls(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(object) => object
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (key, without_nulls(value)))
+            .collect(),
+        serde_json::Value::Array(array) => array.into_iter().map(without_nulls).collect(),
+        value => value,
+    }
+}
+
+/// A value that is persisted to a file.
+///
+/// Mutable access to the value marks it as dirty.
+/// Dirty values are written back to the file atomically on [`Persisted::save`] or when dropped.
+/// Since errors cannot be reported on drop, call [`Persisted::save`] explicitly to handle them.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{FromFile, Persisted};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+/// struct Settings {
+///     verbose: bool,
+///     retries: u32,
+/// }
+///
+/// #[cfg(feature = "yaml")]
+/// {
+///     let filename = std::env::temp_dir().join("serde_rw_persisted.yml");
+///     let _ = std::fs::remove_file(&filename);
+///
+///     let mut settings = Persisted::<Settings>::load(&filename).unwrap();
+///     assert!(!settings.is_dirty());
+///     settings.retries = 3;
+///     assert!(settings.is_dirty());
+///     settings.save().unwrap();
+///
+///     assert_eq!(Settings::from_file(&filename).unwrap().retries, 3);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Persisted<T>
+where
+    T: ToFile,
+{
+    value: T,
+    path: PathBuf,
+    dirty: bool,
+}
+
+impl<T> Persisted<T>
+where
+    T: LoadOrDefault,
+{
+    /// Loads the value from the given file, creating it from the default if it does not exist.
+    ///
+    /// See [`LoadOrDefault::load_or_default`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read, deserialized or created.
+    pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(Self {
+            value: T::load_or_default(path.as_ref())?,
+            path: path.as_ref().to_path_buf(),
+            dirty: false,
+        })
+    }
+}
+
+impl<T> Persisted<T>
+where
+    T: ToFile,
+{
+    /// Wraps a value that has not been persisted to the given file yet.
+    ///
+    /// The value is considered dirty.
+    pub fn new(path: impl AsRef<Path>, value: T) -> Self {
+        Self {
+            value,
+            path: path.as_ref().to_path_buf(),
+            dirty: true,
+        }
+    }
+
+    /// Returns the path of the file the value is persisted to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether the value has been modified since it was last loaded or saved.
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Writes the value to the file atomically if it is dirty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the value cannot be serialized or written.
+    pub fn save(&mut self) -> crate::Result<()> {
+        if self.dirty {
+            create_parent_dir(&self.path)?;
+            self.value.write_to_file_with(
+                &self.path,
+                &WriteOptions::default().pretty(true).atomic(true),
+            )?;
+            self.dirty = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Deref for Persisted<T>
+where
+    T: ToFile,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Persisted<T>
+where
+    T: ToFile,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.dirty = true;
+        &mut self.value
+    }
+}
+
+impl<T> Drop for Persisted<T>
+where
+    T: ToFile,
+{
+    fn drop(&mut self) {
+        let _ = self.save();
+    }
+}
+
+/// Creates the parent directory of the given file, if it does not exist.
+fn create_parent_dir(filename: &Path) -> crate::Result<()> {
+    match filename.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => Ok(create_dir_all(parent)?),
+        _ => Ok(()),
+    }
+}
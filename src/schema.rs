@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use schemars::{JsonSchema, schema_for};
+use serde_json::Value;
+
+#[cfg(feature = "toml")]
+use crate::formats::TOML_DATETIME_FIELD;
+use crate::{Error, ToFile};
+
+/// Validates a value against a JSON Schema.
+///
+/// # Errors
+///
+/// Returns an [`Error::InvalidSchema`] if the schema is invalid
+/// and an [`Error::SchemaViolations`] listing all violations if the value does not conform to the schema.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Error, validate_schema};
+/// use serde_json::json;
+///
+/// let schema = json!({"properties": {"id": {"type": "integer", "maximum": 1000}}});
+/// assert!(validate_schema(&json!({"id": 42}), &schema).is_ok());
+///
+/// let error = validate_schema(&json!({"id": 1337}), &schema).unwrap_err();
+/// assert!(matches!(&error, Error::SchemaViolations(violations) if violations[0].0 == "/id"));
+/// ```
+pub fn validate_schema(value: &Value, schema: &Value) -> crate::Result<()> {
+    let validator =
+        jsonschema::validator_for(schema).map_err(|error| Error::InvalidSchema(Box::new(error)))?;
+
+    #[cfg(feature = "toml")]
+    let value = &with_toml_datetimes_as_strings(value.clone());
+
+    let violations: Vec<_> = validator
+        .iter_errors(value)
+        .map(|error| (error.instance_path().to_string(), error.to_string()))
+        .collect();
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::SchemaViolations(violations))
+    }
+}
+
+/// Writes the JSON Schema of a type to a file.
+///
+/// The format is determined by the file extension, see [`ToFile::write_to_file`].
+/// Editors and linters which support JSON Schema can use the file to check documents of any format.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the schema cannot be serialized or written.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{FromFile, write_schema};
+///
+/// #[cfg(feature = "schema")]
+/// {
+///     #[derive(schemars::JsonSchema, serde::Deserialize)]
+///     struct Person {
+///         id: u32,
+///         name: String,
+///     }
+///
+///     let filename = std::env::temp_dir().join("serde_rw_person.schema.json");
+///     write_schema::<Person>(&filename).unwrap();
+///
+///     let schema = serde_json::Value::from_file(&filename).unwrap();
+///     assert_eq!(schema["required"], serde_json::json!(["id", "name"]));
+/// }
+/// ```
+pub fn write_schema<T>(filename: impl AsRef<Path>) -> crate::Result<()>
+where
+    T: JsonSchema,
+{
+    schema_for!(T).write_to_file_pretty(filename)
+}
+
+/// Replaces TOML datetimes, which appear as single-entry objects, with their string representation,
+/// so that they can be validated against string schemas.
+#[cfg(feature = "toml")]
+fn with_toml_datetimes_as_strings(value: Value) -> Value {
+    match value {
+        Value::Object(mut object)
+            if object.len() == 1
+                && object
+                    .get(TOML_DATETIME_FIELD)
+                    .is_some_and(Value::is_string) =>
+        {
+            object.remove(TOML_DATETIME_FIELD).unwrap_or_default()
+        }
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, with_toml_datetimes_as_strings(value)))
+                .collect(),
+        ),
+        Value::Array(array) => Value::Array(
+            array
+                .into_iter()
+                .map(with_toml_datetimes_as_strings)
+                .collect(),
+        ),
+        value => value,
+    }
+}
@@ -0,0 +1,480 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Formatter};
+
+use serde::Deserialize;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde_ignored::Path;
+
+use crate::{Error, UnknownKey};
+
+/// The maximum edit distance, relative to the key's length, at which a known field is suggested.
+const MAX_RELATIVE_DISTANCE: f64 = 0.34;
+
+/// Returns an [`Error::UnknownKeys`] if there are any unknown keys.
+pub fn deny(unknown_keys: Vec<UnknownKey>) -> crate::Result<()> {
+    if unknown_keys.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::UnknownKeys(unknown_keys))
+    }
+}
+
+/// Deserializes an object and returns the keys of the input that the object did not use.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<(T, Vec<UnknownKey>), D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let state = RefCell::new(State::default());
+    let mut paths = Vec::new();
+    let value = serde_ignored::deserialize(
+        Recorder {
+            inner: deserializer,
+            state: &state,
+            path: String::new(),
+            key: false,
+            index: 0,
+        },
+        |path| paths.push((render(&path), parent(&path))),
+    )?;
+    let fields = state.into_inner().fields;
+    let unknown_keys = paths
+        .into_iter()
+        .map(|(path, parent)| {
+            let suggestion = parent.and_then(|(parent, key)| suggest(&key, fields.get(&parent)?));
+            UnknownKey::new(path, suggestion)
+        })
+        .collect();
+    Ok((value, unknown_keys))
+}
+
+/// Renders a path as map keys separated by dots and sequence indices in brackets,
+/// omitting options and newtypes, which are transparent within the input.
+fn render(path: &Path<'_>) -> String {
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{index}]", render(parent)),
+        Path::Map { parent, key } => match render(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{parent}.{key}"),
+        },
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => render(parent),
+    }
+}
+
+/// Returns the rendered path of the map holding the key at the given path, along with the key.
+fn parent(path: &Path<'_>) -> Option<(String, String)> {
+    match path {
+        Path::Map { parent, key } => Some((render(parent), key.clone())),
+        _ => None,
+    }
+}
+
+/// Joins a rendered path and a map key like [`render`].
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Returns the known field most similar to the given key, if any is similar enough.
+fn suggest(key: &str, fields: &BTreeSet<&'static str>) -> Option<String> {
+    fields
+        .iter()
+        .filter(|field| **field != key)
+        .map(|field| (strsim::damerau_levenshtein(key, field), *field))
+        .filter(|(distance, field)| {
+            #[allow(clippy::cast_precision_loss)]
+            let relative = *distance as f64 / key.chars().count().max(field.chars().count()) as f64;
+            relative <= MAX_RELATIVE_DISTANCE
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| field.to_string())
+}
+
+/// The fields of all structs that were deserialized by their rendered paths
+/// and the most recently deserialized map key.
+#[derive(Default)]
+struct State {
+    fields: BTreeMap<String, BTreeSet<&'static str>>,
+    key: Option<String>,
+}
+
+/// Records the fields of all structs that are deserialized along with their paths.
+///
+/// Wraps deserializers, visitors and seeds alike, which pass on the path of the value being deserialized.
+/// Recorders of map keys store the key, so that the map's recorder can extend the path of the following value.
+struct Recorder<'state, T> {
+    inner: T,
+    state: &'state RefCell<State>,
+    path: String,
+    key: bool,
+    index: usize,
+}
+
+impl<'state, T> Recorder<'state, T> {
+    fn wrap<U>(&self, inner: U) -> Recorder<'state, U> {
+        self.child(inner, self.path.clone(), self.key)
+    }
+
+    const fn child<U>(&self, inner: U, path: String, key: bool) -> Recorder<'state, U> {
+        Recorder {
+            inner,
+            state: self.state,
+            path,
+            key,
+            index: 0,
+        }
+    }
+
+    fn record(&self, fields: &'static [&'static str]) {
+        self.state
+            .borrow_mut()
+            .fields
+            .entry(self.path.clone())
+            .or_default()
+            .extend(fields);
+    }
+
+    fn capture(&self, key: &impl ToString) {
+        if self.key {
+            self.state.borrow_mut().key = Some(key.to_string());
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $type:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let visitor = self.wrap(visitor);
+                self.inner.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D> Deserializer<'de> for Recorder<'_, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.record(fields);
+        let visitor = self.wrap(visitor);
+        self.inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(
+            fn $method<E>(self, value: $type) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.capture(&value);
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V> Visitor<'de> for Recorder<'_, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.capture(&String::from_utf8_lossy(value));
+        self.inner.visit_bytes(value)
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.capture(&String::from_utf8_lossy(value));
+        self.inner.visit_borrowed_bytes(value)
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.capture(&String::from_utf8_lossy(&value));
+        self.inner.visit_byte_buf(value)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let deserializer = self.wrap(deserializer);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let deserializer = self.wrap(deserializer);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let seq = self.child(seq, self.path.clone(), false);
+        self.inner.visit_seq(seq)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let map = self.child(map, self.path.clone(), false);
+        self.inner.visit_map(map)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let data = self.wrap(data);
+        self.inner.visit_enum(data)
+    }
+}
+
+impl<'de, S> DeserializeSeed<'de> for Recorder<'_, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let deserializer = self.wrap(deserializer);
+        self.inner.deserialize(deserializer)
+    }
+}
+
+impl<'de, A> SeqAccess<'de> for Recorder<'_, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let seed = self.child(seed, format!("{}[{}]", self.path, self.index), false);
+        self.index += 1;
+        self.inner.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, A> MapAccess<'de> for Recorder<'_, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.state.borrow_mut().key = None;
+        let seed = self.child(seed, self.path.clone(), true);
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let path = match self.state.borrow_mut().key.take() {
+            Some(key) => join(&self.path, &key),
+            None => self.path.clone(),
+        };
+        let seed = self.child(seed, path, false);
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, 'state, A> EnumAccess<'de> for Recorder<'state, A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = Recorder<'state, A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (state, path) = (self.state, self.path.clone());
+        let seed = self.wrap(seed);
+        let (value, variant) = self.inner.variant_seed(seed)?;
+        Ok((
+            value,
+            Recorder {
+                inner: variant,
+                state,
+                path,
+                key: false,
+                index: 0,
+            },
+        ))
+    }
+}
+
+impl<'de, A> VariantAccess<'de> for Recorder<'_, A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+        self.inner.newtype_variant_seed(seed)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.tuple_variant(len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.record(fields);
+        let visitor = self.wrap(visitor);
+        self.inner.struct_variant(fields, visitor)
+    }
+}
@@ -1,40 +1,30 @@
-use std::ffi::OsStr;
-use std::path::Path;
+use std::ffi::OsString;
+use std::fs::{File, metadata, read, remove_file, rename, write};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use serde::Serialize;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+use serde::de::DeserializeOwned;
 
-use crate::Error;
-
-#[cfg(feature = "xml")]
-const XML_INDENT_CHAR: char = ' ';
-#[cfg(feature = "xml")]
-const XML_INDENT_LEN: usize = 4;
+use crate::compression::{decode, pack};
+use crate::formats::detect;
+use crate::{Comparison, Compression, Format, WriteOptions};
 
 /// Makes an object capable of writing itself to a file of a specified format.
 pub trait ToFile: Serialize + Sized {
     /// Serializes an object into a file dependent on its file extension.
     ///
+    /// If the last file extension denotes a supported compression, e.g. `data.json.gz`,
+    /// the file is compressed and the format is taken from the preceding extension.
+    ///
     /// # Errors
     ///
-    /// Returns an [`Error`] if the serialization fails.
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
     fn write_to_file(&self, filename: impl AsRef<Path>) -> crate::Result<()> {
-        let extension = filename
-            .as_ref()
-            .extension()
-            .map(OsStr::to_ascii_lowercase)
-            .ok_or(Error::NoFileExtensionsSpecified)?;
-
-        match extension.as_encoded_bytes() {
-            #[cfg(feature = "json")]
-            b"json" => <Self as crate::ToJson>::write_to_json_file(self, filename),
-            #[cfg(feature = "toml")]
-            b"toml" => <Self as crate::ToToml>::write_to_toml_file(self, filename),
-            #[cfg(feature = "xml")]
-            b"xml" => <Self as crate::ToXml>::write_to_xml_file(self, filename),
-            #[cfg(feature = "yaml")]
-            b"yml" | b"yaml" => <Self as crate::ToYaml>::write_to_yaml_file(self, filename),
-            _ => Err(Error::UnsupportedFileExtension(extension)),
-        }
+        self.write_to_file_with(filename, &WriteOptions::default())
     }
 
     /// Serializes an object into a prettified file dependent on its file extension.
@@ -43,27 +33,358 @@ pub trait ToFile: Serialize + Sized {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error`] if the serialization fails.
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
     fn write_to_file_pretty(&self, filename: impl AsRef<Path>) -> crate::Result<()> {
-        let extension = filename
-            .as_ref()
-            .extension()
-            .map(OsStr::to_ascii_lowercase)
-            .ok_or(Error::NoFileExtensionsSpecified)?;
-
-        match extension.as_encoded_bytes() {
-            #[cfg(feature = "json")]
-            b"json" => <Self as crate::ToJson>::write_to_json_file_pretty(self, filename),
-            #[cfg(feature = "xml")]
-            b"xml" => <Self as crate::ToXml>::write_to_xml_file_pretty(
-                self,
-                filename,
-                XML_INDENT_CHAR,
-                XML_INDENT_LEN,
-            ),
-            _ => self.write_to_file(filename),
+        self.write_to_file_with(filename, &WriteOptions::default().pretty(true))
+    }
+
+    /// Serializes an object into a file dependent on its file extension using the given options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{FromFile, ToFile, WriteOptions};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "json", feature = "gzip"))]
+    /// {
+    ///     let person = Person {
+    ///         id: 1337,
+    ///         name: "John Doe".to_string(),
+    ///     };
+    ///     let filename = std::env::temp_dir().join("serde_rw_write_to_file_with.json.gz");
+    ///     person
+    ///         .write_to_file_with(&filename, &WriteOptions::default().compression_level(9))
+    ///         .unwrap();
+    ///     assert_eq!(Person::from_file(&filename).unwrap(), person);
+    /// }
+    /// ```
+    fn write_to_file_with(
+        &self,
+        filename: impl AsRef<Path>,
+        options: &WriteOptions,
+    ) -> crate::Result<()> {
+        let filename = filename.as_ref();
+        write_changed(filename, detect(filename)?, self, options).map(drop)
+    }
+
+    /// Serializes an object into a file of the given format using the given options, regardless of its file extension.
+    ///
+    /// If the last file extension denotes a supported compression, e.g. `data.gz`, the file is compressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Format, ToFile, WriteOptions};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "toml")]
+    /// {
+    ///     let person = Person {
+    ///         id: 1337,
+    ///         name: "John Doe".to_string(),
+    ///     };
+    ///     let filename = std::env::temp_dir().join("serde_rw_write_to_file_as.conf");
+    ///     person
+    ///         .write_to_file_as(&filename, Format::Toml, &WriteOptions::default())
+    ///         .unwrap();
+    ///     assert_eq!(
+    ///         std::fs::read_to_string(&filename).unwrap(),
+    ///         "id = 1337\nname = \"John Doe\"\n"
+    ///     );
+    /// }
+    /// ```
+    fn write_to_file_as(
+        &self,
+        filename: impl AsRef<Path>,
+        format: Format,
+        options: &WriteOptions,
+    ) -> crate::Result<()> {
+        let filename = filename.as_ref();
+        let compression = filename.extension().and_then(Compression::from_extension);
+        write_changed(filename, (format, compression), self, options).map(drop)
+    }
+
+    /// Serializes an object into a file, unless the file already holds exactly the content to be written.
+    ///
+    /// Skipping unchanged files retains their modification time and avoids triggering file watchers.
+    /// Returns whether the file was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::ToFile;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(feature = "json")]
+    /// {
+    ///     let person = Person {
+    ///         id: 1337,
+    ///         name: "John Doe".to_string(),
+    ///     };
+    ///     let filename = std::env::temp_dir().join("serde_rw_write_to_file_if_changed.json");
+    ///     let _ = std::fs::remove_file(&filename);
+    ///     assert!(person.write_to_file_if_changed(&filename).unwrap());
+    ///     assert!(!person.write_to_file_if_changed(&filename).unwrap());
+    /// }
+    /// ```
+    fn write_to_file_if_changed(&self, filename: impl AsRef<Path>) -> crate::Result<bool> {
+        self.write_to_file_if_changed_with(filename, &WriteOptions::default())
+    }
+
+    /// Serializes an object into a file using the given write options, unless the file's content is unchanged.
+    ///
+    /// The content is compared as set via [`WriteOptions::if_changed`], or byte-for-byte if unset.
+    /// Returns whether the file was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Comparison, ToFile, WriteOptions};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     id: u32,
+    /// }
+    ///
+    /// #[cfg(feature = "yaml")]
+    /// {
+    ///     let filename = std::env::temp_dir().join("serde_rw_write_to_file_if_changed_with.yml");
+    ///     std::fs::write(&filename, "# A person.\nid: 1337\nname: John Doe\n").unwrap();
+    ///
+    ///     let person = Person {
+    ///         name: "John Doe".to_string(),
+    ///         id: 1337,
+    ///     };
+    ///     let options = WriteOptions::default().if_changed(Comparison::Semantic);
+    ///     assert!(!person.write_to_file_if_changed_with(&filename, &options).unwrap());
+    /// }
+    /// ```
+    fn write_to_file_if_changed_with(
+        &self,
+        filename: impl AsRef<Path>,
+        options: &WriteOptions,
+    ) -> crate::Result<bool> {
+        let filename = filename.as_ref();
+        let options = options.if_changed(options.get_if_changed().unwrap_or_default());
+        write_changed(filename, detect(filename)?, self, &options)
+    }
+
+    /// Compares the content of a file with the object, as it would be written to the file.
+    ///
+    /// This allows showing which values [`ToFile::write_to_file`] is about to change before overwriting a file.
+    /// See [`diff`](crate::diff) for how the content is compared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the file cannot be read or parsed or the serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::ToFile;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[cfg(all(feature = "diff", feature = "json"))]
+    /// {
+    ///     let person = Person {
+    ///         id: 42,
+    ///         name: "John Doe".to_string(),
+    ///     };
+    ///     let diff = person.diff_with_file("./tests/person.json").unwrap();
+    ///     assert_eq!(diff.to_string(), "-id: 1337\n+id: 42\n");
+    /// }
+    /// ```
+    #[cfg(feature = "diff")]
+    fn diff_with_file(&self, filename: impl AsRef<Path>) -> crate::Result<crate::Diff> {
+        use crate::{FromFile, Value};
+
+        let filename = filename.as_ref();
+        let (format, _) = detect(filename)?;
+        let new: Value = format.deserialize(&format.serialize(self, false)?)?;
+        Ok(crate::Diff::between(&Value::from_file(filename)?, &new))
+    }
+}
+
+/// Serializes an object into a file, unless the options request to only write changed files
+/// and the file's content is unchanged.
+///
+/// Returns whether the file was written.
+fn write_changed<T>(
+    filename: &Path,
+    (format, compression): (Format, Option<Compression>),
+    value: &T,
+    options: &WriteOptions,
+) -> crate::Result<bool>
+where
+    T: Serialize,
+{
+    let bytes = encode(value, format, compression, options)?;
+
+    if let Some(comparison) = options.get_if_changed()
+        && let Ok(existing) = read(filename)
+    {
+        let unchanged = match comparison {
+            Comparison::Bytes => existing == bytes,
+            Comparison::Semantic => {
+                let new = decode(bytes.clone(), compression)?;
+                decode(existing, compression).is_ok_and(|old| equivalent(format, &old, &new))
+            }
+        };
+
+        if unchanged {
+            return Ok(false);
+        }
+    }
+
+    write_bytes(filename, &bytes, options)?;
+    Ok(true)
+}
+
+/// Determines whether two texts of the given format hold the same data.
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(unused_variables, clippy::missing_const_for_fn)
+)]
+fn equivalent(format: Format, old: &str, new: &str) -> bool {
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => same::<serde_json::Value>(format, old, new),
+        #[cfg(feature = "toml")]
+        Format::Toml => same::<toml::Table>(format, old, new),
+        #[cfg(feature = "xml")]
+        Format::Xml => old == new,
+        #[cfg(feature = "yaml")]
+        Format::Yaml => same::<serde_yaml::Value>(format, old, new),
+    }
+}
+
+/// Determines whether two texts of the given format deserialize into equal values.
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+fn same<T>(format: Format, old: &str, new: &str) -> bool
+where
+    T: DeserializeOwned + PartialEq,
+{
+    match (format.deserialize::<T>(old), format.deserialize::<T>(new)) {
+        (Ok(old), Ok(new)) => old == new,
+        _ => false,
+    }
+}
+
+/// Writes raw file content to the given file, atomically and keeping a backup if requested by the options.
+pub fn write_bytes(filename: &Path, bytes: &[u8], options: &WriteOptions) -> crate::Result<()> {
+    if let Some(backup) = options.get_backup() {
+        crate::backup::create(filename, backup)?;
+    }
+
+    if !options.is_atomic() {
+        return Ok(write(filename, bytes)?);
+    }
+
+    let temporary = temporary_path(filename);
+    let result = File::create(&temporary).and_then(|mut file| {
+        if let Ok(metadata) = metadata(filename) {
+            file.set_permissions(metadata.permissions())?;
         }
+
+        file.write_all(bytes)?;
+        file.sync_all()
+    });
+
+    if let Err(error) = result.and_then(|()| rename(&temporary, filename)) {
+        let _ = remove_file(&temporary);
+        return Err(error.into());
     }
+
+    Ok(())
+}
+
+/// Returns a unique path for a temporary file next to the given file.
+pub fn temporary_path(filename: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let mut name = filename
+        .file_name()
+        .map(|name| {
+            let mut hidden = OsString::from(".");
+            hidden.push(name);
+            hidden
+        })
+        .unwrap_or_default();
+    name.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    filename.with_file_name(name)
+}
+
+/// Serializes an object into raw file content dependent on the given file name's extension.
+#[cfg_attr(
+    not(any(feature = "tokio", feature = "zip", feature = "tar")),
+    allow(dead_code)
+)]
+pub fn to_bytes<T>(filename: &Path, value: &T, options: &WriteOptions) -> crate::Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let (format, compression) = detect(filename)?;
+    encode(value, format, compression, options)
+}
+
+/// Serializes an object into raw file content of the given format and compression.
+fn encode<T>(
+    value: &T,
+    format: Format,
+    compression: Option<Compression>,
+    options: &WriteOptions,
+) -> crate::Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let text = format.serialize_with(value, options)?;
+    pack(
+        text.into_bytes(),
+        compression,
+        options.get_compression_level(),
+    )
 }
 
 impl<T> ToFile for T where T: Serialize {}
@@ -0,0 +1,612 @@
+use std::cell::RefCell;
+use std::fmt::{self, Formatter};
+use std::fs::read;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+#[cfg_attr(
+    not(any(feature = "json", feature = "xml", feature = "yaml")),
+    allow(unused_imports)
+)]
+use serde_transcode::Transcoder;
+
+#[cfg(any(feature = "toml", feature = "xml"))]
+use crate::Value;
+use crate::compression::pack;
+use crate::formats::{detect, detect_content, serialize};
+use crate::to_file::write_bytes;
+use crate::{Compression, Error, Format, Unrepresentable, WriteOptions};
+
+/// Converts a file into another format without deserializing it into a concrete type.
+///
+/// The formats and compressions are determined by the file extensions, see
+/// [`FromFile::from_file`](crate::FromFile::from_file) and [`ToFile::write_to_file`](crate::ToFile::write_to_file).
+/// XML output is enclosed by a root element named after the output file, e.g. `person` for `person.xml`,
+/// unless [`WriteOptions::xml_root`] names it.
+///
+/// Formats other than TOML receive TOML datetimes as strings,
+/// and formats other than XML receive XML elements holding nothing but text as that text,
+/// see [`Value::with_text_unwrapped`].
+///
+/// # Errors
+///
+/// Returns an [`Error::Unrepresentable`] if the input contains a value the output format cannot express,
+/// e.g. `null` values or a top-level value other than a table in TOML or non-scalar keys in JSON.
+/// Returns an [`Error::Transcode`] if the input cannot be parsed.
+/// Returns an [`Error`] if the files cannot be read or written.
+///
+/// # Examples
+/// ```
+/// use serde_rw::transcode;
+///
+/// #[cfg(all(feature = "json", feature = "yaml"))]
+/// {
+///     let output = std::env::temp_dir().join("serde_rw_transcode.json");
+///     transcode("./tests/person.yml", &output).unwrap();
+///     assert_eq!(
+///         std::fs::read_to_string(output).unwrap(),
+///         r#"{"id":1337,"name":"John Doe"}"#
+///     );
+/// }
+///
+/// #[cfg(all(feature = "json", feature = "xml"))]
+/// {
+///     let output = std::env::temp_dir().join("person.xml");
+///     transcode("./tests/person.json", &output).unwrap();
+///     assert_eq!(
+///         std::fs::read_to_string(output).unwrap(),
+///         "<person><id>1337</id><name>John Doe</name></person>"
+///     );
+/// }
+/// ```
+pub fn transcode(input: impl AsRef<Path>, output: impl AsRef<Path>) -> crate::Result<()> {
+    transcode_with(input, output, &WriteOptions::default())
+}
+
+/// Converts a file into another format using the given write options.
+///
+/// See [`transcode`] for details.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the file cannot be transcoded, read or written.
+pub fn transcode_with(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &WriteOptions,
+) -> crate::Result<()> {
+    let (from, text) = detect_content(input.as_ref(), read(input.as_ref())?)?;
+    let (to, compression) = detect(output.as_ref())?;
+    let stem = root_name(output.as_ref(), compression);
+    let bytes = transcode_text(
+        &text,
+        from,
+        &Target {
+            format: to,
+            compression,
+            options,
+            root: options.get_xml_root().or(stem.as_deref()),
+        },
+    )?;
+    write_bytes(output.as_ref(), &bytes, options)
+}
+
+/// Converts the content of a reader in one format into another format written to a writer.
+///
+/// The options' compression level is ignored, since streams are not compressed.
+/// XML output requires the name of its root element to be given by [`WriteOptions::xml_root`].
+/// See [`transcode`] for how datetimes and XML text are converted.
+///
+/// # Errors
+///
+/// Returns an [`Error::Unrepresentable`] if the input cannot be expressed in the output format,
+/// including any value in XML if the name of the root element is missing,
+/// and an [`Error::Transcode`] if it cannot be parsed.
+/// Returns an [`Error`] if reading or writing fails.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Format, WriteOptions, transcode_stream};
+///
+/// #[cfg(all(feature = "json", feature = "toml"))]
+/// {
+///     let mut output = Vec::new();
+///     transcode_stream(
+///         r#"{"id": 1337, "name": "John Doe"}"#.as_bytes(),
+///         Format::Json,
+///         &mut output,
+///         Format::Toml,
+///         &WriteOptions::default(),
+///     )
+///     .unwrap();
+///     assert_eq!(
+///         String::from_utf8(output).unwrap(),
+///         "id = 1337\nname = \"John Doe\"\n"
+///     );
+///
+///     let error = transcode_stream(
+///         r#"{"id": null}"#.as_bytes(),
+///         Format::Json,
+///         &mut Vec::new(),
+///         Format::Toml,
+///         &WriteOptions::default(),
+///     )
+///     .unwrap_err();
+///     assert!(matches!(
+///         error,
+///         serde_rw::Error::Unrepresentable { ref path, value: serde_rw::Unrepresentable::Null, .. }
+///             if path == "id"
+///     ));
+///     assert_eq!(
+///         error.to_string(),
+///         "Cannot transcode JSON to TOML: TOML cannot represent the null value at id"
+///     );
+/// }
+///
+/// #[cfg(all(feature = "json", feature = "toml"))]
+/// {
+///     let mut output = Vec::new();
+///     transcode_stream(
+///         "when = 1979-05-27T07:32:00Z".as_bytes(),
+///         Format::Toml,
+///         &mut output,
+///         Format::Json,
+///         &WriteOptions::default(),
+///     )
+///     .unwrap();
+///     assert_eq!(
+///         String::from_utf8(output).unwrap(),
+///         r#"{"when":"1979-05-27T07:32:00Z"}"#
+///     );
+/// }
+/// ```
+pub fn transcode_stream<R, W>(
+    mut reader: R,
+    from: Format,
+    mut writer: W,
+    to: Format,
+    options: &WriteOptions,
+) -> crate::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let bytes = transcode_text(
+        &text,
+        from,
+        &Target {
+            format: to,
+            compression: None,
+            options,
+            root: options.get_xml_root(),
+        },
+    )?;
+    writer.write_all(&bytes)?;
+    Ok(writer.flush()?)
+}
+
+/// Returns the name of the output file without its extensions, which names the root element of XML output.
+fn root_name(output: &Path, compression: Option<Compression>) -> Option<String> {
+    let stem = Path::new(output.file_stem()?);
+    let stem = if compression.is_some() {
+        stem.file_stem()?
+    } else {
+        stem.as_os_str()
+    };
+    stem.to_str().map(ToString::to_string)
+}
+
+/// The format, compression, options and XML root element to transcode into.
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(dead_code)
+)]
+struct Target<'options> {
+    format: Format,
+    compression: Option<Compression>,
+    options: &'options WriteOptions,
+    root: Option<&'options str>,
+}
+
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(dead_code)
+)]
+impl Target<'_> {
+    /// Encodes the output of a transcoder.
+    #[cfg_attr(
+        not(any(feature = "json", feature = "xml", feature = "yaml")),
+        allow(dead_code)
+    )]
+    fn transcode<T>(&self, transcoder: &T) -> crate::Result<Vec<u8>>
+    where
+        T: Serialize,
+    {
+        // The TOML serializer serializes values more than once, which the transcoder does not support.
+        #[cfg(feature = "toml")]
+        if self.format == Format::Toml {
+            return self.encode(&toml::Value::try_from(transcoder)?);
+        }
+
+        self.encode(transcoder)
+    }
+
+    /// Encodes a value which can be serialized more than once.
+    fn encode<T>(&self, value: &T) -> crate::Result<Vec<u8>>
+    where
+        T: Serialize,
+    {
+        self.pack(serialize(
+            self.format,
+            value,
+            self.options.is_pretty(),
+            self.root,
+        )?)
+    }
+
+    fn pack(&self, text: String) -> crate::Result<Vec<u8>> {
+        pack(
+            text.into_bytes(),
+            self.compression,
+            self.options.get_compression_level(),
+        )
+    }
+}
+
+/// Feeds the deserializer of the text's format into the target's serializer.
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(unused_variables, unreachable_code)
+)]
+fn transcode_text(text: &str, from: Format, target: &Target<'_>) -> crate::Result<Vec<u8>> {
+    check(text, from, target)?;
+    let result: crate::Result<Vec<u8>> = match from {
+        // The TOML deserializer passes on datetimes as maps, which only dynamic values turn back into datetimes,
+        // so that they are written as such into TOML and as strings into other formats.
+        #[cfg(feature = "toml")]
+        Format::Toml => from
+            .deserialize::<Value>(text)
+            .and_then(|value| target.encode(&value)),
+        // Only XML distinguishes the text of elements from nested elements, which other formats receive as their text.
+        #[cfg(feature = "xml")]
+        Format::Xml if target.format != Format::Xml => from
+            .deserialize::<Value>(text)
+            .and_then(|value| target.encode(&value.with_text_unwrapped())),
+        #[cfg(feature = "json")]
+        Format::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(text);
+            target
+                .transcode(&Transcoder::new(&mut deserializer))
+                .and_then(|bytes| Ok(deserializer.end().map(|()| bytes)?))
+        }
+        #[cfg(feature = "xml")]
+        Format::Xml => target.transcode(&Transcoder::new(
+            &mut quick_xml::de::Deserializer::from_str(text),
+        )),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            target.transcode(&Transcoder::new(serde_yaml::Deserializer::from_str(text)))
+        }
+    };
+
+    result.map_err(|error| match error {
+        Error::Serde(source) => Error::Transcode {
+            from,
+            to: target.format,
+            source,
+        },
+        error => error,
+    })
+}
+
+/// Ensures that the target format can represent all values of the text,
+/// so that such values are reported along with their paths rather than by the serializer.
+///
+/// Texts that cannot be parsed are left to the transcoder, which reports the parse error.
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(unused_variables, unreachable_code)
+)]
+fn check(text: &str, from: Format, target: &Target<'_>) -> crate::Result<()> {
+    let to = target.format;
+    let unrepresentable = |(path, value)| Error::Unrepresentable {
+        from,
+        to,
+        path,
+        value,
+    };
+
+    #[cfg(feature = "xml")]
+    if to == Format::Xml && target.root.is_none() {
+        return Err(unrepresentable((String::new(), Unrepresentable::TopLevel)));
+    }
+
+    let found = RefCell::new(None);
+    let checker = Checker {
+        to,
+        path: String::new(),
+        found: &found,
+    };
+    let _ = match from {
+        #[cfg(feature = "json")]
+        Format::Json => checker
+            .deserialize(&mut serde_json::Deserializer::from_str(text))
+            .is_ok(),
+        #[cfg(feature = "toml")]
+        Format::Toml => toml::Deserializer::parse(text)
+            .is_ok_and(|deserializer| checker.deserialize(deserializer).is_ok()),
+        #[cfg(feature = "xml")]
+        Format::Xml => checker
+            .deserialize(&mut quick_xml::de::Deserializer::from_str(text))
+            .is_ok(),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => checker
+            .deserialize(serde_yaml::Deserializer::from_str(text))
+            .is_ok(),
+    };
+
+    found
+        .into_inner()
+        .map_or(Ok(()), |found| Err(unrepresentable(found)))
+}
+
+#[cfg(feature = "toml")]
+const fn is_toml(format: Format) -> bool {
+    matches!(format, Format::Toml)
+}
+
+#[cfg(not(feature = "toml"))]
+const fn is_toml(_: Format) -> bool {
+    false
+}
+
+/// Visits all values of a deserializer without storing them,
+/// recording the first one which the target format cannot represent.
+///
+/// Visiting stops with an error once such a value has been found.
+struct Checker<'found> {
+    to: Format,
+    path: String,
+    found: &'found RefCell<Option<(String, Unrepresentable)>>,
+}
+
+impl Checker<'_> {
+    const fn child(&self, path: String) -> Self {
+        Self {
+            to: self.to,
+            path,
+            found: self.found,
+        }
+    }
+
+    fn found<E>(self, value: Unrepresentable) -> Result<(), E>
+    where
+        E: serde::de::Error,
+    {
+        *self.found.borrow_mut() = Some((self.path, value));
+        Err(E::custom(format!(
+            "{} cannot represent the {value}",
+            self.to
+        )))
+    }
+
+    /// Records a scalar or sequence if the target format requires a table at the top level.
+    fn top_level<E>(self) -> Result<(), E>
+    where
+        E: serde::de::Error,
+    {
+        if is_toml(self.to) && self.path.is_empty() {
+            return self.found(Unrepresentable::TopLevel);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Checker<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+macro_rules! visit_scalar {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(
+            fn $method<E>(self, _: $type) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.top_level()
+            }
+        )*
+    };
+}
+
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "xml", feature = "yaml")),
+    allow(unreachable_code, unused_mut)
+)]
+impl<'de> Visitor<'de> for Checker<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    visit_scalar! {
+        visit_bool(bool),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f64(f64),
+        visit_str(&str),
+        visit_bytes(&[u8]),
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if is_toml(self.to) {
+            let value = if self.path.is_empty() {
+                Unrepresentable::TopLevel
+            } else {
+                Unrepresentable::Null
+            };
+            return self.found(value);
+        }
+
+        Ok(())
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut index = 0;
+
+        while seq
+            .next_element_seed(self.child(format!("{}[{index}]", self.path)))?
+            .is_some()
+        {
+            index += 1;
+        }
+
+        self.top_level()
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key_seed(KeyChecker)? {
+            let representable = match (self.to, &key) {
+                (_, Key::String(_)) => true,
+                #[cfg(feature = "json")]
+                (Format::Json, Key::Scalar(_)) => true,
+                _ => false,
+            };
+
+            if !representable {
+                return self.found(Unrepresentable::Key);
+            }
+
+            let (Key::String(key) | Key::Scalar(key) | Key::Other(key)) = key;
+            let path = if self.path.is_empty() {
+                key
+            } else {
+                format!("{}.{key}", self.path)
+            };
+            map.next_value_seed(self.child(path))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A map key, as far as formats distinguish them.
+enum Key {
+    String(String),
+    Scalar(String),
+    Other(String),
+}
+
+/// Deserializes a map key, ignoring the content of keys which are neither strings nor scalars.
+struct KeyChecker;
+
+impl<'de> DeserializeSeed<'de> for KeyChecker {
+    type Value = Key;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+macro_rules! visit_scalar_key {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(
+            fn $method<E>(self, key: $type) -> Result<Self::Value, E> {
+                Ok(Key::Scalar(key.to_string()))
+            }
+        )*
+    };
+}
+
+impl<'de> Visitor<'de> for KeyChecker {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map key")
+    }
+
+    visit_scalar_key! {
+        visit_bool(bool),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f64(f64),
+    }
+
+    fn visit_str<E>(self, key: &str) -> Result<Self::Value, E> {
+        Ok(Key::String(key.to_string()))
+    }
+
+    fn visit_bytes<E>(self, _: &[u8]) -> Result<Self::Value, E> {
+        Ok(Key::Other("<bytes>".to_string()))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Key::Other("null".to_string()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(Key::Other("<sequence>".to_string()))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(Key::Other("<map>".to_string()))
+    }
+}
@@ -0,0 +1,316 @@
+use crate::Error;
+
+/// The field name under which the `validator` crate reports struct-level errors.
+#[cfg(feature = "validator")]
+const VALIDATOR_STRUCT_FIELD: &str = "__all__";
+
+/// Makes an object capable of checking constraints, which its type cannot express, after deserialization.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Error, FromFile, Validate, Violations};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Person {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// impl Validate for Person {
+///     fn validate(&self, violations: &mut Violations) {
+///         violations.ensure(self.id < 1000, "id", "must be less than 1000");
+///         violations.ensure(!self.name.contains(' '), "name", "must be a single word");
+///     }
+/// }
+///
+/// #[cfg(feature = "json")]
+/// {
+///     let error = Person::from_file_checked("./tests/person.json").unwrap_err();
+///     assert_eq!(
+///         error.to_string(),
+///         "Validation failed:\n  id: must be less than 1000\n  name: must be a single word"
+///     );
+/// }
+/// ```
+pub trait Validate {
+    /// Checks the object's constraints and records every violation.
+    fn validate(&self, violations: &mut Violations);
+
+    /// Checks the object's constraints and returns all violations as an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Validation`] holding all violations.
+    fn check(&self) -> crate::Result<()> {
+        let mut violations = Violations::new();
+        self.validate(&mut violations);
+        violations.into_result()
+    }
+}
+
+/// A list of constraint violations, each consisting of a field path and a message.
+///
+/// Field paths of nested values are separated by dots.
+/// Violations concerning an object as a whole, e.g. of cross-field rules, have an empty path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Violations(Vec<(String, String)>);
+
+impl Violations {
+    /// Creates an empty list of violations.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Records a violation of the field at the given path.
+    pub fn push(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push((path.into(), message.into()));
+    }
+
+    /// Records a violation of the field at the given path unless the condition holds.
+    pub fn ensure(&mut self, condition: bool, path: impl Into<String>, message: impl Into<String>) {
+        if !condition {
+            self.push(path, message);
+        }
+    }
+
+    /// Records the violations of a nested value with their paths prefixed by the given field path.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{Validate, Violations};
+    ///
+    /// struct Port(u16);
+    ///
+    /// impl Validate for Port {
+    ///     fn validate(&self, violations: &mut Violations) {
+    ///         violations.ensure(self.0 != 0, "", "must not be zero");
+    ///     }
+    /// }
+    ///
+    /// struct Server {
+    ///     ports: Vec<Port>,
+    /// }
+    ///
+    /// impl Validate for Server {
+    ///     fn validate(&self, violations: &mut Violations) {
+    ///         for (index, port) in self.ports.iter().enumerate() {
+    ///             violations.nested(format!("ports[{index}]"), port);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let error = Server { ports: vec![Port(80), Port(0)] }.check().unwrap_err();
+    /// assert_eq!(error.to_string(), "Validation failed:\n  ports[1]: must not be zero");
+    /// ```
+    pub fn nested<T>(&mut self, path: impl AsRef<str>, value: &T)
+    where
+        T: Validate + ?Sized,
+    {
+        let mut nested = Self::new();
+        value.validate(&mut nested);
+        self.0.extend(
+            nested
+                .0
+                .into_iter()
+                .map(|(field, message)| (join(path.as_ref(), &field), message)),
+        );
+    }
+
+    /// Returns whether no violations have been recorded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of recorded violations.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns an iterator over the field paths and messages of the recorded violations.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(path, message)| (path.as_str(), message.as_str()))
+    }
+
+    /// Returns an [`Error::Validation`] if any violations have been recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Validation`] holding all recorded violations unless there are none.
+    pub fn into_result(self) -> crate::Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(self))
+        }
+    }
+}
+
+impl Extend<(String, String)> for Violations {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        self.0.extend(iter);
+    }
+}
+
+impl IntoIterator for Violations {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Converts the errors reported by the `validator` crate into violations sorted by their field paths.
+///
+/// This allows types deriving `validator::Validate` to implement [`Validate`] by delegation.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Validate, Violations};
+///
+/// #[cfg(feature = "validator")]
+/// {
+///     struct Person {
+///         id: u32,
+///     }
+///
+///     impl validator::Validate for Person {
+///         fn validate(&self) -> Result<(), validator::ValidationErrors> {
+///             let mut errors = validator::ValidationErrors::new();
+///
+///             if self.id >= 1000 {
+///                 errors.add(
+///                     "id",
+///                     validator::ValidationError::new("range").with_message("too large".into()),
+///                 );
+///             }
+///
+///             if errors.is_empty() { Ok(()) } else { Err(errors) }
+///         }
+///     }
+///
+///     impl Validate for Person {
+///         fn validate(&self, violations: &mut Violations) {
+///             if let Err(errors) = validator::Validate::validate(self) {
+///                 violations.extend(Violations::from(errors));
+///             }
+///         }
+///     }
+///
+///     let error = Person { id: 1337 }.check().unwrap_err();
+///     assert_eq!(error.to_string(), "Validation failed:\n  id: too large");
+/// }
+/// ```
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrors> for Violations {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let mut violations = Self::new();
+        flatten_validator_errors(&mut violations, "", errors);
+        violations.0.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        violations
+    }
+}
+
+#[cfg(feature = "validator")]
+fn flatten_validator_errors(
+    violations: &mut Violations,
+    path: &str,
+    errors: validator::ValidationErrors,
+) {
+    for (field, kind) in errors.into_errors() {
+        let field = if field == VALIDATOR_STRUCT_FIELD {
+            path.to_string()
+        } else {
+            join(path, &field)
+        };
+
+        match kind {
+            validator::ValidationErrorsKind::Field(errors) => {
+                for error in errors {
+                    violations.push(field.clone(), error.to_string());
+                }
+            }
+            validator::ValidationErrorsKind::Struct(errors) => {
+                flatten_validator_errors(violations, &field, *errors);
+            }
+            validator::ValidationErrorsKind::List(list) => {
+                for (index, errors) in list {
+                    flatten_validator_errors(violations, &format!("{field}[{index}]"), *errors);
+                }
+            }
+        }
+    }
+}
+
+/// Converts the report of the `garde` crate into violations.
+///
+/// This allows types deriving `garde::Validate` to implement [`Validate`] by delegation.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{Validate, Violations};
+///
+/// #[cfg(feature = "garde")]
+/// {
+///     struct Person {
+///         id: u32,
+///     }
+///
+///     impl garde::Validate for Person {
+///         type Context = ();
+///
+///         fn validate_into(
+///             &self,
+///             _: &Self::Context,
+///             parent: &mut dyn FnMut() -> garde::Path,
+///             report: &mut garde::Report,
+///         ) {
+///             if self.id >= 1000 {
+///                 report.append(parent().join("id"), garde::Error::new("too large"));
+///             }
+///         }
+///     }
+///
+///     impl Validate for Person {
+///         fn validate(&self, violations: &mut Violations) {
+///             if let Err(report) = garde::Validate::validate(self) {
+///                 violations.extend(Violations::from(report));
+///             }
+///         }
+///     }
+///
+///     let error = Person { id: 1337 }.check().unwrap_err();
+///     assert_eq!(error.to_string(), "Validation failed:\n  id: too large");
+/// }
+/// ```
+#[cfg(feature = "garde")]
+impl From<garde::Report> for Violations {
+    fn from(report: garde::Report) -> Self {
+        Self(
+            report
+                .into_inner()
+                .into_iter()
+                .map(|(path, error)| (path.to_string(), error.message().to_string()))
+                .collect(),
+        )
+    }
+}
+
+/// Joins a field path and a nested field path.
+fn join(path: &str, field: &str) -> String {
+    match (path, field) {
+        ("", field) => field.to_string(),
+        (path, "") => path.to_string(),
+        (path, field) if field.starts_with('[') => format!("{path}{field}"),
+        (path, field) => format!("{path}.{field}"),
+    }
+}
@@ -0,0 +1,795 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Formatter};
+
+use indexmap::IndexMap;
+use serde::de::{EnumAccess, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+#[cfg(feature = "toml")]
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "yaml")),
+    allow(unused_imports)
+)]
+use crate::Error;
+use crate::formats::TOML_DATETIME_FIELD;
+#[cfg(feature = "toml")]
+use crate::formats::TOML_DATETIME_NAME;
+use crate::path::{Segment, out_of_bounds, segments};
+
+/// The key under which XML elements hold their text content.
+const XML_TEXT_KEY: &str = "$text";
+
+/// An ordered map of [`Value`]s.
+pub type Map = IndexMap<String, Value>;
+
+/// A dynamically typed value of any supported format.
+///
+/// Unlike the value types of the individual format crates, it retains the order of map entries,
+/// the width of integers, TOML datetimes and raw bytes, so that values can be converted between formats
+/// without losing information that the target format is able to express.
+///
+/// Datetimes are written as such by this crate's TOML writers, e.g. [`ToFile`](crate::ToFile) or
+/// [`ToToml`](crate::ToToml), and as strings holding their RFC 3339 representation otherwise,
+/// including by serializers of other crates, which cannot tell datetimes from strings.
+/// YAML values which cannot be represented, i.e. non-string map keys and tagged values, are rejected.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{FromFile, Value};
+///
+/// #[cfg(feature = "toml")]
+/// {
+///     let mut value = Value::from_file("./tests/person.toml").unwrap();
+///     assert_eq!(value.get_path("name"), Some(&Value::from("John Doe")));
+///
+///     value.set_path("address.city", Value::from("Springfield")).unwrap();
+///     assert_eq!(
+///         value.get_path("address.city").and_then(Value::as_str),
+///         Some("Springfield")
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    /// The absence of a value.
+    #[default]
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// An integer of a specific width.
+    Integer(Integer),
+    /// A floating point number of a specific width.
+    Float(Float),
+    /// A string.
+    String(String),
+    /// Raw bytes.
+    Bytes(Vec<u8>),
+    /// A TOML datetime in its RFC 3339 representation.
+    Datetime(String),
+    /// An ordered sequence of values.
+    Sequence(Vec<Self>),
+    /// An ordered map of string keys to values.
+    Map(Map),
+}
+
+/// An integer which retains its width and signedness.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Integer {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+}
+
+/// A floating point number which retains its width.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Float {
+    F32(f32),
+    F64(f64),
+}
+
+impl Integer {
+    /// Returns the integer as `i128`, if it fits.
+    #[must_use]
+    pub fn as_i128(self) -> Option<i128> {
+        match self {
+            Self::I8(int) => Some(int.into()),
+            Self::I16(int) => Some(int.into()),
+            Self::I32(int) => Some(int.into()),
+            Self::I64(int) => Some(int.into()),
+            Self::I128(int) => Some(int),
+            Self::U8(int) => Some(int.into()),
+            Self::U16(int) => Some(int.into()),
+            Self::U32(int) => Some(int.into()),
+            Self::U64(int) => Some(int.into()),
+            Self::U128(int) => int.try_into().ok(),
+        }
+    }
+
+    /// Returns the integer as `i64`, if it fits.
+    #[must_use]
+    pub fn as_i64(self) -> Option<i64> {
+        self.as_i128().and_then(|int| int.try_into().ok())
+    }
+
+    /// Returns the integer as `u64`, if it fits.
+    #[must_use]
+    pub fn as_u64(self) -> Option<u64> {
+        match self {
+            Self::U128(int) => int.try_into().ok(),
+            int => int.as_i128().and_then(|int| int.try_into().ok()),
+        }
+    }
+}
+
+impl Float {
+    /// Returns the number as `f64`.
+    #[must_use]
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Self::F32(float) => float.into(),
+            Self::F64(float) => float,
+        }
+    }
+}
+
+impl Value {
+    /// Returns the string, if this is a string or a datetime.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) | Self::Datetime(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, if this is a boolean.
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer as `i64`, if this is an integer which fits.
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(int) => int.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer as `u64`, if this is an integer which fits.
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Integer(int) => int.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as `f64`, if this is a floating point number.
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Float(float) => Some(float.as_f64()),
+            _ => None,
+        }
+    }
+
+    /// Returns the sequence, if this is a sequence.
+    #[must_use]
+    pub const fn as_sequence(&self) -> Option<&Vec<Self>> {
+        match self {
+            Self::Sequence(sequence) => Some(sequence),
+            _ => None,
+        }
+    }
+
+    /// Returns the map, if this is a map.
+    #[must_use]
+    pub const fn as_map(&self) -> Option<&Map> {
+        match self {
+            Self::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this is [`Value::Null`].
+    #[must_use]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Replaces all datetimes with strings holding their RFC 3339 representation.
+    ///
+    /// This writes them as strings into TOML files as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{ToToml, Value};
+    ///
+    /// #[cfg(feature = "toml")]
+    /// {
+    ///     let value: Value = toml::from_str("date = 1979-05-27T07:32:00Z").unwrap();
+    ///     assert_eq!(value.to_toml().unwrap(), "date = 1979-05-27T07:32:00Z\n");
+    ///     assert_eq!(
+    ///         value.with_datetimes_as_strings().to_toml().unwrap(),
+    ///         "date = \"1979-05-27T07:32:00Z\"\n"
+    ///     );
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_datetimes_as_strings(self) -> Self {
+        match self {
+            Self::Datetime(datetime) => Self::String(datetime),
+            Self::Sequence(sequence) => Self::Sequence(
+                sequence
+                    .into_iter()
+                    .map(Self::with_datetimes_as_strings)
+                    .collect(),
+            ),
+            Self::Map(map) => Self::Map(
+                map.into_iter()
+                    .map(|(key, value)| (key, value.with_datetimes_as_strings()))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Replaces all maps holding nothing but XML text content, i.e. a single `$text` entry, with their content.
+    ///
+    /// XML elements are read as such maps, which other formats would write as nested maps.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde_rw::{FromXml, Value};
+    ///
+    /// #[cfg(feature = "xml")]
+    /// {
+    ///     let value = Value::from_xml_string(r#"<person><name>John Doe</name><id kind="user">1337</id></person>"#)
+    ///         .unwrap()
+    ///         .with_text_unwrapped();
+    ///     assert_eq!(value.get_path("name"), Some(&Value::from("John Doe")));
+    ///     assert_eq!(value.get_path("id.$text"), Some(&Value::from("1337")));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_text_unwrapped(self) -> Self {
+        match self {
+            Self::Sequence(sequence) => Self::Sequence(
+                sequence
+                    .into_iter()
+                    .map(Self::with_text_unwrapped)
+                    .collect(),
+            ),
+            Self::Map(mut map) if map.len() == 1 && map.contains_key(XML_TEXT_KEY) => {
+                map.swap_remove(XML_TEXT_KEY).unwrap_or_default()
+            }
+            Self::Map(map) => Self::Map(
+                map.into_iter()
+                    .map(|(key, value)| (key, value.with_text_unwrapped()))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Returns the value at the given path, if it exists.
+    ///
+    /// Paths consist of map keys separated by dots and sequence indices in brackets, e.g. `servers[0].host`.
+    /// Numeric keys, e.g. `servers.0.host`, index sequences as well.
+    #[must_use]
+    pub fn get_path(&self, path: &str) -> Option<&Self> {
+        segments(path)
+            .ok()?
+            .into_iter()
+            .try_fold(self, |value, segment| match (value, segment) {
+                (Self::Map(map), Segment::Key(key)) => map.get(key),
+                (Self::Sequence(sequence), Segment::Index(index)) => sequence.get(index),
+                (Self::Sequence(sequence), Segment::Key(key)) => {
+                    sequence.get(key.parse::<usize>().ok()?)
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns a mutable reference to the value at the given path, if it exists.
+    ///
+    /// See [`Value::get_path`] for the path syntax.
+    #[must_use]
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Self> {
+        segments(path)
+            .ok()?
+            .into_iter()
+            .try_fold(self, |value, segment| match (value, segment) {
+                (Self::Map(map), Segment::Key(key)) => map.get_mut(key),
+                (Self::Sequence(sequence), Segment::Index(index)) => sequence.get_mut(index),
+                (Self::Sequence(sequence), Segment::Key(key)) => {
+                    sequence.get_mut(key.parse::<usize>().ok()?)
+                }
+                _ => None,
+            })
+    }
+
+    /// Sets the value at the given path and returns the previous value, if any.
+    ///
+    /// Missing maps along the path are created and values that are neither maps nor sequences are replaced by maps.
+    /// Indexing one past the end of a sequence appends to it.
+    /// See [`Value::get_path`] for the path syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidPath`] if the path is malformed or a sequence index is out of bounds.
+    pub fn set_path(&mut self, path: &str, value: Self) -> crate::Result<Option<Self>> {
+        let segments = segments(path)?;
+        let Some((last, parents)) = segments.split_last() else {
+            return Ok(Some(std::mem::replace(self, value)));
+        };
+        let mut target = self;
+
+        for segment in parents {
+            target = child(target, segment, path)?;
+        }
+
+        match (target, last.index()) {
+            (Self::Sequence(sequence), Some(index)) => match index.cmp(&sequence.len()) {
+                Ordering::Less => Ok(Some(std::mem::replace(&mut sequence[index], value))),
+                Ordering::Equal => {
+                    sequence.push(value);
+                    Ok(None)
+                }
+                Ordering::Greater => Err(out_of_bounds(path, index)),
+            },
+            (target, _) => Ok(as_map(target).insert(last.key(), value)),
+        }
+    }
+}
+
+/// Returns the child of the given value at the segment, creating it if necessary.
+fn child<'value>(
+    value: &'value mut Value,
+    segment: &Segment<'_>,
+    path: &str,
+) -> crate::Result<&'value mut Value> {
+    match (value, segment.index()) {
+        (Value::Sequence(sequence), Some(index)) => {
+            if index == sequence.len() {
+                sequence.push(Value::Null);
+            }
+
+            sequence
+                .get_mut(index)
+                .ok_or_else(|| out_of_bounds(path, index))
+        }
+        (value, _) => Ok(as_map(value).entry(segment.key()).or_default()),
+    }
+}
+
+/// Returns the map of the given value, replacing the value by an empty map if it is not a map.
+fn as_map(value: &mut Value) -> &mut Map {
+    if !matches!(value, Value::Map(_)) {
+        *value = Value::Map(Map::new());
+    }
+
+    match value {
+        Value::Map(map) => map,
+        _ => unreachable!("value has been converted into a map"),
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(bool) => serializer.serialize_bool(*bool),
+            Self::Integer(int) => int.serialize(serializer),
+            Self::Float(Float::F32(float)) => serializer.serialize_f32(*float),
+            Self::Float(Float::F64(float)) => serializer.serialize_f64(*float),
+            Self::String(string) => serializer.serialize_str(string),
+            Self::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            // Serde has no notion of datetimes, so they are passed on in TOML's representation
+            // to this crate's TOML serializers, which recognize it even if wrapped by other serializers.
+            #[cfg(feature = "toml")]
+            Self::Datetime(datetime) if crate::formats::toml::is_serializing() => {
+                let mut serializer = serializer.serialize_struct(TOML_DATETIME_NAME, 1)?;
+                serializer.serialize_field(TOML_DATETIME_FIELD, datetime)?;
+                serializer.end()
+            }
+            Self::Datetime(datetime) => serializer.serialize_str(datetime),
+            Self::Sequence(sequence) => serializer.collect_seq(sequence),
+            Self::Map(map) => {
+                let mut serializer = serializer.serialize_map(Some(map.len()))?;
+
+                for (key, value) in map {
+                    serializer.serialize_entry(key, value)?;
+                }
+
+                serializer.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Integer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Self::I8(int) => serializer.serialize_i8(int),
+            Self::I16(int) => serializer.serialize_i16(int),
+            Self::I32(int) => serializer.serialize_i32(int),
+            Self::I64(int) => serializer.serialize_i64(int),
+            Self::I128(int) => serializer.serialize_i128(int),
+            Self::U8(int) => serializer.serialize_u8(int),
+            Self::U16(int) => serializer.serialize_u16(int),
+            Self::U32(int) => serializer.serialize_u32(int),
+            Self::U64(int) => serializer.serialize_u64(int),
+            Self::U128(int) => serializer.serialize_u128(int),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Visits any value of serde's data model.
+struct ValueVisitor;
+
+macro_rules! visit_integer {
+    ($($method:ident($type:ty) => $variant:ident),* $(,)?) => {
+        $(
+            fn $method<E>(self, value: $type) -> Result<Self::Value, E> {
+                Ok(Value::Integer(Integer::$variant(value)))
+            }
+        )*
+    };
+}
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    visit_integer! {
+        visit_i8(i8) => I8,
+        visit_i16(i16) => I16,
+        visit_i32(i32) => I32,
+        visit_i64(i64) => I64,
+        visit_i128(i128) => I128,
+        visit_u8(u8) => U8,
+        visit_u16(u16) => U16,
+        visit_u32(u32) => U32,
+        visit_u64(u64) => U64,
+        visit_u128(u128) => U128,
+    }
+
+    fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E> {
+        Ok(Value::Float(Float::F32(value)))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float(Float::F64(value)))
+    }
+
+    fn visit_char<E>(self, value: char) -> Result<Self::Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(value))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut sequence = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+
+        while let Some(element) = seq.next_element()? {
+            sequence.push(element);
+        }
+
+        Ok(Value::Sequence(sequence))
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut map = Map::with_capacity(access.size_hint().unwrap_or_default());
+
+        while let Some(key) = access.next_key::<Value>()? {
+            let key = match key {
+                Value::String(key) if key == TOML_DATETIME_FIELD && map.is_empty() => {
+                    return Ok(Value::Datetime(access.next_value()?));
+                }
+                key => map_key(key).map_err(serde::de::Error::custom)?,
+            };
+            map.insert(key, access.next_value()?);
+        }
+
+        Ok(Value::Map(map))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (tag, _) = data.variant::<String>()?;
+        Err(serde::de::Error::custom(format!(
+            "unsupported tagged value: {tag}"
+        )))
+    }
+}
+
+/// Returns the map key, if it is a string.
+///
+/// Other keys are rejected rather than converted into strings, which could not be converted back.
+fn map_key(key: Value) -> Result<String, String> {
+    match key {
+        Value::String(key) => Ok(key),
+        key => Err(format!("unsupported non-string map key: {key:?}")),
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Integer(Integer::I64(value))
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Self::Integer(Integer::U64(value))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(Float::F64(value))
+    }
+}
+
+impl From<Vec<Self>> for Value {
+    fn from(value: Vec<Self>) -> Self {
+        Self::Sequence(value)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(value: Map) -> Self {
+        Self::Map(value)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Self::Null,
+            serde_json::Value::Bool(bool) => Self::Bool(bool),
+            serde_json::Value::Number(number) => number
+                .as_u64()
+                .map(Integer::U64)
+                .or_else(|| number.as_i64().map(Integer::I64))
+                .map_or_else(
+                    || Self::Float(Float::F64(number.as_f64().unwrap_or(f64::NAN))),
+                    Self::Integer,
+                ),
+            serde_json::Value::String(string) => Self::String(string),
+            serde_json::Value::Array(array) => {
+                Self::Sequence(array.into_iter().map(Self::from).collect())
+            }
+            serde_json::Value::Object(object) => Self::Map(
+                object
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Converts a value into a JSON value.
+///
+/// Bytes become arrays of numbers and datetimes become strings.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if an integer or floating point number is not representable in JSON.
+#[cfg(feature = "json")]
+impl TryFrom<Value> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Ok(serde_json::to_value(value.with_datetimes_as_strings())?)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<toml::Value> for Value {
+    fn from(value: toml::Value) -> Self {
+        match value {
+            toml::Value::String(string) => Self::String(string),
+            toml::Value::Integer(int) => Self::Integer(Integer::I64(int)),
+            toml::Value::Float(float) => Self::Float(Float::F64(float)),
+            toml::Value::Boolean(bool) => Self::Bool(bool),
+            toml::Value::Datetime(datetime) => Self::Datetime(datetime.to_string()),
+            toml::Value::Array(array) => {
+                Self::Sequence(array.into_iter().map(Self::from).collect())
+            }
+            toml::Value::Table(table) => Self::Map(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Converts a value into a TOML value.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the value contains `null`, an integer exceeding the range of `i64`
+/// or an invalid datetime, none of which TOML can represent.
+#[cfg(feature = "toml")]
+impl TryFrom<Value> for toml::Value {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        to_toml(value)
+    }
+}
+
+/// Converts a value into a TOML value.
+///
+/// This is a free function, since the inherent `toml::Value::try_from` shadows the trait method.
+#[cfg(feature = "toml")]
+fn to_toml(value: Value) -> crate::Result<toml::Value> {
+    Ok(match value {
+        Value::Null => return Err(Error::Serde("TOML cannot represent null values".into())),
+        Value::Bool(bool) => toml::Value::Boolean(bool),
+        Value::Integer(int) => toml::Value::Integer(int.as_i64().ok_or_else(|| {
+            Error::Serde(format!("{int:?} exceeds the range of TOML integers").into())
+        })?),
+        Value::Float(float) => toml::Value::Float(float.as_f64()),
+        Value::String(string) => toml::Value::String(string),
+        Value::Bytes(bytes) => toml::Value::Array(
+            bytes
+                .into_iter()
+                .map(|byte| toml::Value::Integer(byte.into()))
+                .collect(),
+        ),
+        Value::Datetime(datetime) => toml::Value::Datetime(
+            datetime
+                .parse()
+                .map_err(|error| Error::Serde(Box::new(error)))?,
+        ),
+        Value::Sequence(sequence) => toml::Value::Array(
+            sequence
+                .into_iter()
+                .map(to_toml)
+                .collect::<crate::Result<_>>()?,
+        ),
+        Value::Map(map) => toml::Value::Table(
+            map.into_iter()
+                .map(|(key, value)| Ok((key, to_toml(value)?)))
+                .collect::<crate::Result<_>>()?,
+        ),
+    })
+}
+
+#[cfg(feature = "yaml")]
+impl TryFrom<serde_yaml::Value> for Value {
+    type Error = Error;
+
+    /// Converts a YAML value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the value contains non-string map keys or tagged values,
+    /// which a [`Value`] cannot represent.
+    fn try_from(value: serde_yaml::Value) -> Result<Self, Self::Error> {
+        Ok(Self::deserialize(value)?)
+    }
+}
+
+/// Converts a value into a YAML value.
+///
+/// Datetimes become strings.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if an integer is not representable in YAML.
+#[cfg(feature = "yaml")]
+impl TryFrom<Value> for serde_yaml::Value {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Ok(serde_yaml::to_value(value.with_datetimes_as_strings())?)
+    }
+}
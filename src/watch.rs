@@ -0,0 +1,297 @@
+use std::ffi::OsString;
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, channel};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+use std::{fmt, thread};
+
+use notify::event::{Event, EventKind};
+use notify::{Config, PollWatcher, RecursiveMode, Watcher, recommended_watcher};
+
+use crate::{Error, FromFile};
+
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(100);
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+type ChangeCallback<T> = Box<dyn Fn(&Arc<T>) + Send>;
+type ErrorCallback = Box<dyn Fn(&Error) + Send>;
+
+/// A value that is loaded from a file and reloaded whenever the file changes.
+///
+/// The file is watched via the platform's native file system notifications,
+/// falling back to polling if those are unavailable.
+/// Since the file's parent directory is watched, editors that save files
+/// by renaming a temporary file over the target are supported as well.
+///
+/// If the changed file cannot be deserialized, the last successfully loaded value is retained.
+///
+/// # Examples
+/// ```
+/// use serde_rw::{ToFile, Watched};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// struct Person {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[cfg(feature = "json")]
+/// {
+///     let filename = std::env::temp_dir().join("serde_rw_watched.json");
+///     Person {
+///         id: 1337,
+///         name: "John Doe".to_string(),
+///     }
+///     .write_to_file(&filename)
+///     .unwrap();
+///
+///     let person = Watched::<Person>::builder(&filename)
+///         .on_change(|person| println!("Reloaded: {person:?}"))
+///         .on_error(|error| eprintln!("Reloading failed: {error}"))
+///         .build()
+///         .unwrap();
+///     assert_eq!(person.get().id, 1337);
+/// }
+/// ```
+pub struct Watched<T> {
+    current: Arc<RwLock<Arc<T>>>,
+    path: PathBuf,
+    _watcher: Box<dyn Watcher + Send>,
+}
+
+impl<T> Watched<T>
+where
+    T: FromFile + Send + Sync + 'static,
+{
+    /// Loads the given file and starts watching it with the default settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be loaded or watched.
+    pub fn new(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Self::builder(path).build()
+    }
+
+    /// Returns a builder to customize how the given file is watched.
+    #[must_use]
+    pub fn builder(path: impl AsRef<Path>) -> WatchedBuilder<T> {
+        WatchedBuilder {
+            path: path.as_ref().to_path_buf(),
+            debounce: DEFAULT_DEBOUNCE,
+            poll_interval: None,
+            on_change: None,
+            on_error: None,
+        }
+    }
+}
+
+impl<T> Watched<T> {
+    /// Returns a snapshot of the current value.
+    #[must_use]
+    pub fn get(&self) -> Arc<T> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the path of the watched file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<T> Debug for Watched<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watched")
+            .field("current", &self.get())
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builder for [`Watched`] values.
+pub struct WatchedBuilder<T> {
+    path: PathBuf,
+    debounce: Duration,
+    poll_interval: Option<Duration>,
+    on_change: Option<ChangeCallback<T>>,
+    on_error: Option<ErrorCallback>,
+}
+
+impl<T> WatchedBuilder<T>
+where
+    T: FromFile + Send + Sync + 'static,
+{
+    /// Sets the period of quiescence to wait for after a change before reloading the file.
+    ///
+    /// Defaults to 100 milliseconds.
+    #[must_use]
+    pub const fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Forces polling the file with the given interval instead of using native notifications.
+    #[must_use]
+    pub const fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets a callback that is invoked with the new value after the file has been reloaded.
+    #[must_use]
+    pub fn on_change(mut self, callback: impl Fn(&Arc<T>) + Send + 'static) -> Self {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets a callback that is invoked if reloading or watching the file fails.
+    #[must_use]
+    pub fn on_error(mut self, callback: impl Fn(&Error) + Send + 'static) -> Self {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Loads the file and starts watching it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be loaded or watched.
+    pub fn build(self) -> crate::Result<Watched<T>> {
+        let current = Arc::new(RwLock::new(Arc::new(T::from_file(&self.path)?)));
+        let directory = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let (sender, receiver) = channel();
+        let mut watcher: Box<dyn Watcher + Send> = match self.poll_interval {
+            Some(interval) => Box::new(PollWatcher::new(
+                sender,
+                Config::default().with_poll_interval(interval),
+            )?),
+            None => match recommended_watcher(sender.clone()) {
+                Ok(watcher) => Box::new(watcher),
+                Err(_) => Box::new(PollWatcher::new(
+                    sender,
+                    Config::default().with_poll_interval(DEFAULT_POLL_INTERVAL),
+                )?),
+            },
+        };
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        let reloader = Reloader {
+            current: current.clone(),
+            path: self.path.clone(),
+            file_name: self.path.file_name().map(ToOwned::to_owned),
+            debounce: self.debounce,
+            on_change: self.on_change,
+            on_error: self.on_error,
+        };
+        thread::Builder::new()
+            .name("serde_rw-watch".to_string())
+            .spawn(move || reloader.run(&receiver))?;
+
+        Ok(Watched {
+            current,
+            path: self.path,
+            _watcher: watcher,
+        })
+    }
+}
+
+impl<T> Debug for WatchedBuilder<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchedBuilder")
+            .field("path", &self.path)
+            .field("debounce", &self.debounce)
+            .field("poll_interval", &self.poll_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reloads the watched file on the background thread.
+struct Reloader<T> {
+    current: Arc<RwLock<Arc<T>>>,
+    path: PathBuf,
+    file_name: Option<OsString>,
+    debounce: Duration,
+    on_change: Option<ChangeCallback<T>>,
+    on_error: Option<ErrorCallback>,
+}
+
+impl<T> Reloader<T>
+where
+    T: FromFile,
+{
+    /// Processes events until the watcher, and thus the sender, is dropped.
+    fn run(&self, receiver: &Receiver<notify::Result<Event>>) {
+        while let Ok(event) = receiver.recv() {
+            if !self.is_relevant(event) {
+                continue;
+            }
+
+            // Wait for the file to settle, since editors tend to write files in multiple steps.
+            // Only events affecting the file extend the wait, so that busy directories cannot delay reloads forever.
+            let mut deadline = Instant::now() + self.debounce;
+
+            loop {
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(event) => {
+                        if self.is_relevant(event) {
+                            deadline = Instant::now() + self.debounce;
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+
+            self.reload();
+        }
+    }
+
+    /// Determines whether the event affects the watched file, reporting watcher errors.
+    fn is_relevant(&self, event: notify::Result<Event>) -> bool {
+        match event {
+            Ok(event) => {
+                !matches!(event.kind, EventKind::Access(_))
+                    && event
+                        .paths
+                        .iter()
+                        .any(|path| path.file_name() == self.file_name.as_deref())
+            }
+            Err(error) => {
+                self.error(&error.into());
+                false
+            }
+        }
+    }
+
+    fn reload(&self) {
+        match T::from_file(&self.path) {
+            Ok(value) => {
+                let value = Arc::new(value);
+                *self.current.write().unwrap_or_else(PoisonError::into_inner) = value.clone();
+
+                if let Some(on_change) = &self.on_change {
+                    on_change(&value);
+                }
+            }
+            Err(error) => self.error(&error),
+        }
+    }
+
+    fn error(&self, error: &Error) {
+        if let Some(on_error) = &self.on_error {
+            on_error(error);
+        }
+    }
+}
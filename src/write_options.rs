@@ -0,0 +1,127 @@
+use crate::Backup;
+
+/// Options to customize how [`ToFile`](crate::ToFile) writes files.
+///
+/// # Examples
+/// ```
+/// use serde_rw::WriteOptions;
+///
+/// let options = WriteOptions::default().pretty(true).compression_level(9);
+/// assert!(options.is_pretty());
+/// assert_eq!(options.get_compression_level(), Some(9));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WriteOptions {
+    pretty: bool,
+    compression_level: Option<u32>,
+    atomic: bool,
+    if_changed: Option<Comparison>,
+    backup: Option<Backup>,
+    xml_root: Option<&'static str>,
+}
+
+impl WriteOptions {
+    /// Sets whether the output shall be prettified, if the format supports it.
+    #[must_use]
+    pub const fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Sets the compression level to use when writing compressed files.
+    ///
+    /// See [`Compression::compress`](crate::Compression::compress) for details.
+    #[must_use]
+    pub const fn compression_level(mut self, level: u32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Sets whether the file shall be written atomically.
+    ///
+    /// If set, the content is written to a temporary file in the same directory,
+    /// which then replaces the target file, so that readers never observe a partially written file.
+    #[must_use]
+    pub const fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Sets that the file shall only be written if its content changes.
+    ///
+    /// The serialized object is compared with the existing file as specified by the comparison,
+    /// so that unchanged files keep their modification time and do not trigger file watchers.
+    #[must_use]
+    pub const fn if_changed(mut self, comparison: Comparison) -> Self {
+        self.if_changed = Some(comparison);
+        self
+    }
+
+    /// Sets that the previous version of an existing file shall be kept before it is overwritten.
+    ///
+    /// See [`Backup`] for where previous versions are kept
+    /// and [`FromFile::restore_backup`](crate::FromFile::restore_backup) for how to load them.
+    #[must_use]
+    pub const fn backup(mut self, backup: Backup) -> Self {
+        self.backup = Some(backup);
+        self
+    }
+
+    /// Sets the name of the root element that encloses the object in XML files.
+    ///
+    /// XML documents require a single root element, which serializers otherwise derive from the name of the object's type,
+    /// so this is needed for values without a name of their own, e.g. maps or [`Value`](crate::Value)s.
+    #[must_use]
+    pub const fn xml_root(mut self, name: &'static str) -> Self {
+        self.xml_root = Some(name);
+        self
+    }
+
+    /// Returns whether the output shall be prettified.
+    #[must_use]
+    pub const fn is_pretty(&self) -> bool {
+        self.pretty
+    }
+
+    /// Returns the compression level, if any.
+    #[must_use]
+    pub const fn get_compression_level(&self) -> Option<u32> {
+        self.compression_level
+    }
+
+    /// Returns whether the file shall be written atomically.
+    #[must_use]
+    pub const fn is_atomic(&self) -> bool {
+        self.atomic
+    }
+
+    /// Returns how the content is compared with the existing file, if the file shall only be written if it changes.
+    #[must_use]
+    pub const fn get_if_changed(&self) -> Option<Comparison> {
+        self.if_changed
+    }
+
+    /// Returns how previous versions of the file shall be kept, if at all.
+    #[must_use]
+    pub const fn get_backup(&self) -> Option<Backup> {
+        self.backup
+    }
+
+    /// Returns the name of the root element in XML files, if any.
+    #[must_use]
+    pub const fn get_xml_root(&self) -> Option<&'static str> {
+        self.xml_root
+    }
+}
+
+/// Specifies how [`WriteOptions::if_changed`] compares the serialized object with an existing file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Comparison {
+    /// The file is unchanged if its bytes equal the bytes that would be written.
+    #[default]
+    Bytes,
+    /// The file is unchanged if it holds the same data, regardless of formatting, comments and key order.
+    ///
+    /// XML files are compared by their text, since there is no format-independent representation of XML.
+    Semantic,
+}
@@ -0,0 +1,100 @@
+//! Tests of the `serde-rw` binary.
+#![allow(unused_crate_dependencies)]
+#![cfg(all(feature = "cli", feature = "toml"))]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const TOML: &str = "zeta = 1\nalpha = 1979-05-27T07:32:00Z\n";
+
+fn serde_rw(args: &[&str], stdin: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_serde-rw"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn fmt_keeps_datetimes_and_key_order() {
+    assert_eq!(serde_rw(&["fmt", "--format", "toml", "-"], TOML), TOML);
+}
+
+#[test]
+fn convert_keeps_datetimes_and_key_order() {
+    let filename = std::env::temp_dir().join("serde_rw_cli_convert.toml");
+    std::fs::write(&filename, TOML).unwrap();
+    serde_rw(
+        &[
+            "convert",
+            filename.to_str().unwrap(),
+            filename.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert_eq!(std::fs::read_to_string(&filename).unwrap(), TOML);
+
+    assert_eq!(
+        serde_rw(
+            &["convert", "--from", "toml", "--to", "json", "-", "-"],
+            TOML
+        ),
+        "{\"zeta\":1,\"alpha\":\"1979-05-27T07:32:00Z\"}\n"
+    );
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn convert_writes_files_in_the_format_of_their_extension() {
+    let input = std::env::temp_dir().join("serde_rw_cli_convert_from.conf");
+    let output = std::env::temp_dir().join("serde_rw_cli_convert_from.toml");
+    std::fs::write(&input, "zeta: 1\nalpha: two\n").unwrap();
+    serde_rw(
+        &[
+            "convert",
+            "--from",
+            "yaml",
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        "zeta = 1\nalpha = \"two\"\n"
+    );
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn convert_names_the_xml_root_element_after_the_output_file() {
+    let output = std::env::temp_dir().join("person.xml");
+    serde_rw(
+        &["convert", "--from", "toml", "-", output.to_str().unwrap()],
+        TOML,
+    );
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        "<person><zeta>1</zeta><alpha>1979-05-27T07:32:00Z</alpha></person>"
+    );
+
+    assert_eq!(
+        serde_rw(
+            &[
+                "convert", "--from", "toml", "--to", "xml", "--root", "config", "-", "-"
+            ],
+            TOML
+        ),
+        "<config><zeta>1</zeta><alpha>1979-05-27T07:32:00Z</alpha></config>\n"
+    );
+}
@@ -0,0 +1,59 @@
+//! Tests of reloading watched files.
+#![allow(unused_crate_dependencies)]
+#![cfg(all(feature = "watch", feature = "json"))]
+
+use std::fs::{create_dir_all, remove_dir_all, rename, write};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_rw::Watched;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Person {
+    id: u32,
+}
+
+/// Waits for the next reloaded value.
+fn reloaded(changes: &Receiver<u32>) -> u32 {
+    changes
+        .recv_timeout(TIMEOUT)
+        .expect("file was not reloaded")
+}
+
+#[test]
+fn reloads_changed_files_and_keeps_the_last_valid_value() {
+    let dir = std::env::temp_dir().join("serde_rw_watch");
+    let _ = remove_dir_all(&dir);
+    create_dir_all(&dir).unwrap();
+    let filename = dir.join("person.json");
+    write(&filename, r#"{"id": 1}"#).unwrap();
+
+    let (change_sender, changes) = channel();
+    let (error_sender, errors) = channel();
+    let person = Watched::<Person>::builder(&filename)
+        .debounce(Duration::from_millis(50))
+        .on_change(move |person| change_sender.send(person.id).unwrap())
+        .on_error(move |error| error_sender.send(error.to_string()).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(person.get().id, 1);
+
+    write(&filename, r#"{"id": 2}"#).unwrap();
+    assert_eq!(reloaded(&changes), 2);
+    assert_eq!(person.get().id, 2);
+
+    write(&filename, r#"{"id": "#).unwrap();
+    errors
+        .recv_timeout(TIMEOUT)
+        .expect("error was not reported");
+    assert_eq!(person.get().id, 2);
+
+    let temporary = dir.join("person.json.tmp");
+    write(&temporary, r#"{"id": 3}"#).unwrap();
+    rename(&temporary, &filename).unwrap();
+    assert_eq!(reloaded(&changes), 3);
+    assert_eq!(person.get().id, 3);
+}